@@ -2,7 +2,7 @@ use crate::types::*;
 use scrypto::prelude::*;
 
 #[blueprint]
-#[types(BadgeData, String, TxData)]
+#[types(BadgeData, String, TxData, Decimal, YearData)]
 mod badge_manager {
     struct BadgeManager {
         admin_manager: ResourceManager, // Mints an admin nft with contract data
@@ -10,6 +10,7 @@ mod badge_manager {
         txs: KeyValueStore<String, TxData>, // Stores history of all tx data
         txs_total: Decimal,             // total number of txs
         years: HashSet<Decimal>,        // which years transactions have taken place
+        year_data: KeyValueStore<Decimal, YearData>, // tx ids & totals of each year
         kind: ContractKind,             // kind of contract: Project | Job
     }
 
@@ -45,12 +46,15 @@ mod badge_manager {
                 &format!("{kind_str} Admin: {name}"),
                 "Admin nft containing information on the contract",
                 &auth_rule,
+                true,
             );
 
             let member_manager = Self::nft_builder::<BadgeData>(
                 &format!("{kind_str} Member: {name}"),
                 "Member nft containing information on the contract",
                 &auth_rule,
+                // proves membership in check_proof, so it stays with whoever joined
+                false,
             );
 
             Self {
@@ -59,6 +63,7 @@ mod badge_manager {
                 txs: KeyValueStore::<String, TxData>::new_with_registered_type(),
                 txs_total: dec!(0),
                 years: HashSet::new(),
+                year_data: KeyValueStore::<Decimal, YearData>::new_with_registered_type(),
                 kind,
             }
             .instantiate()
@@ -72,6 +77,10 @@ mod badge_manager {
             self.admin_manager.address()
         }
 
+        pub fn member_badge(&self) -> ResourceAddress {
+            self.member_manager.address()
+        }
+
         pub fn create_admin_nft(
             &mut self,
             handle: String,
@@ -95,12 +104,103 @@ mod badge_manager {
         pub fn create_tx(&mut self, tx_data: TxData) {
             let new_total = self.txs_total + 1;
             self.txs_total = new_total;
+            let tx_type = tx_data.tx_type.clone();
+            let resource_address = tx_data.resource_address;
+            let amount = tx_data.amount;
             self.txs.insert(format!("{new_total}"), tx_data);
 
             let year = Self::get_year();
             if !self.years.contains(&year) {
                 self.years.insert(year);
+                self.year_data.insert(
+                    year,
+                    YearData {
+                        first_tx: new_total,
+                        last_tx: new_total,
+                        deposited: HashMap::new(),
+                        rewarded: HashMap::new(),
+                        withdrawn: HashMap::new(),
+                        cancelled: HashMap::new(),
+                    },
+                );
             }
+
+            // txs are created in order, so the year's range only grows
+            let mut year_data = self.year_data.get_mut(&year).unwrap();
+            year_data.last_tx = new_total;
+            // only fungible amounts are totalled, non-fungibles are kept by id in the txs
+            let resource_address = match resource_address {
+                Some(resource_address) if resource_address.is_fungible() => resource_address,
+                _ => return,
+            };
+            let totals = match tx_type {
+                TxType::Deposit => &mut year_data.deposited,
+                // a resolved dispute pays the member's share like a reward
+                TxType::Reward | TxType::Resolve => &mut year_data.rewarded,
+                TxType::Withdraw => &mut year_data.withdrawn,
+                TxType::Cancellation => &mut year_data.cancelled,
+                _ => return,
+            };
+            let total = totals.entry(resource_address).or_insert(dec!(0));
+            *total = *total + amount;
+        }
+
+        /// Returns a page of transactions, optionally filtered
+        ///
+        /// Filters are applied within the index range, so a filtered page can hold fewer
+        /// than `end - start` txs; keep paging by index until `get_total` is reached
+        ///
+        /// # Arguments
+        ///
+        /// * `start` - Index of the first tx, starting at 1
+        /// * `end` - Index after the last tx; at most `MAX_PAGE` after `start`
+        /// * `tx_type` - Only return txs of this type
+        /// * `badge` - Only return txs sent from or to this badge
+        ///
+        /// # Returns
+        ///
+        /// * `Vec<TxData>` - The txs within the range, in the order created
+        ///
+        pub fn get_txs(
+            &self,
+            start: u64,
+            end: u64,
+            tx_type: Option<TxType>,
+            badge: Option<ResourceAddress>,
+        ) -> Vec<TxData> {
+            assert!(start > 0 && end >= start, "[Get Txs]: Invalid range");
+            assert!(end - start <= MAX_PAGE, "[Get Txs]: Range too large");
+
+            let mut txs = Vec::new();
+            for index in start..end {
+                let tx_data = match self.txs.get(&format!("{index}")) {
+                    Some(tx_data) => (*tx_data).clone(),
+                    None => break,
+                };
+                let is_type = tx_type.as_ref().map_or(true, |t| &tx_data.tx_type == t);
+                let is_badge =
+                    badge.map_or(true, |b| tx_data.from_badge == b || tx_data.to_badge == b);
+                if is_type && is_badge {
+                    txs.push(tx_data);
+                }
+            }
+            txs
+        }
+
+        pub fn get_total(&self) -> Decimal {
+            self.txs_total
+        }
+
+        pub fn get_year_data(&self, year: Decimal) -> Option<YearData> {
+            self.year_data
+                .get(&year)
+                .map(|year_data| (*year_data).clone())
+        }
+
+        pub fn get_years(&self) -> Vec<Decimal> {
+            let mut years: Vec<Decimal> = self.years.iter().cloned().collect();
+            years.sort();
+            years
         }
 
         //
@@ -111,7 +211,13 @@ mod badge_manager {
             name: &str,
             description: &str,
             access_rule: &AccessRule,
+            is_transferable: bool,
         ) -> ResourceManager {
+            let withdraw_rule = if is_transferable {
+                rule!(allow_all)
+            } else {
+                rule!(deny_all)
+            };
             ResourceBuilder::new_string_non_fungible_with_registered_type::<D>(OwnerRole::None)
                 .metadata(metadata! {
                     init {
@@ -126,6 +232,10 @@ mod badge_manager {
                     minter => access_rule.clone();
                     minter_updater => rule!(deny_all);
                 })
+                .withdraw_roles(withdraw_roles! {
+                    withdrawer => withdraw_rule;
+                    withdrawer_updater => rule!(deny_all);
+                })
                 .create_with_no_initial_supply()
         }
 
@@ -1,5 +1,5 @@
 use crate::list::list::List;
-use crate::types::ContractKind;
+use crate::types::{ContractKind, ListingFee, ListingState};
 use scrypto::prelude::*;
 
 #[blueprint]
@@ -8,9 +8,14 @@ mod category {
         name: String,
         kind: ContractKind,
         minimum: Decimal,
-        fee: Decimal,
+        fees: HashMap<ResourceAddress, ListingFee>,
         resource_address: ResourceAddress,
+        is_active: bool,
+        // removed categories no longer track listing states
+        is_removed: bool,
+        curator_badge: Option<ResourceAddress>,
         list: Owned<List>,
+        states: KeyValueStore<ComponentAddress, ListingState>,
         details: KeyValueStore<String, String>,
     }
 
@@ -19,16 +24,21 @@ mod category {
             name: String,
             kind: ContractKind,
             minimum: Decimal,
-            fee: Decimal,
+            fees: HashMap<ResourceAddress, ListingFee>,
             resource_address: ResourceAddress,
         ) -> Owned<Category> {
+            Self::check_fees(&fees, &resource_address);
             let component = Self {
                 name,
                 kind,
                 minimum,
-                fee,
+                fees,
                 resource_address,
+                is_active: true,
+                is_removed: false,
+                curator_badge: None,
                 list: List::new(),
+                states: KeyValueStore::new(),
                 details: KeyValueStore::new(),
             }
             .instantiate();
@@ -36,33 +46,173 @@ mod category {
             component
         }
 
-        pub fn check_contract(
-            &self,
-            contract_amount: Decimal,
-            contract_resource: ResourceAddress,
-        ) -> Decimal {
+        pub fn check_contract(&self, contract_amount: Decimal, contract_resource: ResourceAddress) {
+            assert!(self.is_active, "[Mint]: Category not active");
             assert!(contract_amount >= self.minimum, "[Mint]: Less than minimum");
             assert!(
                 contract_resource == self.resource_address,
                 "[Mint]: Different resource"
             );
-            self.fee
+        }
+
+        pub fn get_fee(&self, contract_amount: Decimal, fee_resource: ResourceAddress) -> Decimal {
+            let fee = self.fees.get(&fee_resource);
+            assert!(fee.is_some(), "[Fee]: Resource not accepted");
+            match fee.unwrap() {
+                ListingFee::Flat(fee) => *fee,
+                ListingFee::Percent { percent, min, max } => {
+                    // rounded up so the fee can be paid in the resource's smallest unit
+                    let divisibility = match ResourceManager::from(fee_resource).resource_type() {
+                        ResourceType::Fungible { divisibility } => divisibility,
+                        ResourceType::NonFungible { .. } => 0,
+                    };
+                    let mut fee = (contract_amount * *percent / dec!(100))
+                        .checked_round(divisibility, RoundingMode::ToPositiveInfinity)
+                        .unwrap();
+                    if let Some(min) = min {
+                        fee = fee.max(*min);
+                    }
+                    if let Some(max) = max {
+                        fee = fee.min(*max);
+                    }
+                    fee
+                }
+            }
         }
 
         pub fn list(&mut self, address: ComponentAddress) {
             self.list.add(address);
+            self.states.insert(address, ListingState::Active);
+        }
+
+        /// Filled & closed contracts are removed from the active list until reopened
+        pub fn set_state(&mut self, address: ComponentAddress, state: ListingState) {
+            if self.is_removed || !self.list.contains(address) {
+                return;
+            }
+            match state {
+                ListingState::Active => self.list.restore(address),
+                ListingState::Filled | ListingState::Closed => self.list.remove(address),
+            }
+            self.states.insert(address, state);
+        }
+
+        pub fn get_state(&self, address: ComponentAddress) -> Option<ListingState> {
+            self.states.get(&address).map(|state| (*state).clone())
         }
 
         pub fn remove(&mut self, address: ComponentAddress) {
             self.list.remove(address);
         }
 
-        pub fn update(&mut self, name: String, minimum: Decimal, details: HashMap<String, String>) {
-            self.name = name;
+        pub fn contains(&self, address: ComponentAddress) -> bool {
+            self.list.contains(address)
+        }
+
+        /// Removes the contracts within the index range so they can be listed in another category
+        pub fn take_contracts(&mut self, start: u64, end: u64) -> Vec<ComponentAddress> {
+            self.list.take_page(start, end)
+        }
+
+        /// Lists a contract moved from a removed category in its current state
+        ///
+        /// Returns false if the contract doesn't meet the category's resource or minimum,
+        /// closed contracts are only checked for the resource
+        ///
+        pub fn migrate(
+            &mut self,
+            address: ComponentAddress,
+            state: ListingState,
+            contract_amount: Decimal,
+            contract_resource: ResourceAddress,
+        ) -> bool {
+            let is_valid = contract_resource == self.resource_address
+                && (state == ListingState::Closed || contract_amount >= self.minimum);
+            if is_valid {
+                self.list(address);
+                self.set_state(address, state);
+            }
+            is_valid
+        }
+
+        pub fn get_contracts(&self, start: u64, end: u64) -> Vec<ComponentAddress> {
+            self.list.get_page(start, end)
+        }
+
+        pub fn get_counts(&self) -> (Decimal, Decimal) {
+            self.list.get_counts()
+        }
+
+        pub fn update(
+            &mut self,
+            minimum: Decimal,
+            fees: HashMap<ResourceAddress, ListingFee>,
+            resource_address: ResourceAddress,
+            is_active: bool,
+            details: HashMap<String, String>,
+        ) {
+            // listed contracts were checked against the current resource
+            let (active, removed) = self.list.get_counts();
+            assert!(
+                resource_address == self.resource_address || active + removed == dec!(0),
+                "[Update]: Can't change the resource with listings"
+            );
+            Self::check_fees(&fees, &resource_address);
             self.minimum = minimum;
+            self.fees = fees;
+            self.resource_address = resource_address;
+            self.is_active = is_active;
             for (key, value) in details.iter() {
                 self.details.insert(key.to_owned(), value.to_owned());
             }
         }
+
+        /// Only called by the marketplace when the store key is moved
+        pub fn rename(&mut self, name: String) {
+            self.name = name;
+        }
+
+        pub fn set_curator(&mut self, curator_badge: Option<ResourceAddress>) {
+            self.curator_badge = curator_badge;
+        }
+
+        pub fn get_curator(&self) -> Option<ResourceAddress> {
+            self.curator_badge
+        }
+
+        pub fn retire(&mut self) {
+            self.is_active = false;
+            self.is_removed = true;
+        }
+
+        fn check_fees(
+            fees: &HashMap<ResourceAddress, ListingFee>,
+            resource_address: &ResourceAddress,
+        ) {
+            assert!(!fees.is_empty(), "[Fee]: No fee resources");
+            for (fee_resource, fee) in fees.iter() {
+                match fee {
+                    ListingFee::Flat(fee) => assert!(fee >= &dec!(0), "[Fee]: Invalid fee"),
+                    ListingFee::Percent { percent, min, max } => {
+                        // a percent of the contract amount is only paid in the same resource
+                        assert!(
+                            fee_resource == resource_address,
+                            "[Fee]: Percent must be in the contract resource"
+                        );
+                        assert!(
+                            percent > &dec!(0) && percent <= &dec!(100),
+                            "[Fee]: Invalid percent"
+                        );
+                        assert!(
+                            min.unwrap_or(dec!(0)) >= dec!(0) && max.unwrap_or(dec!(0)) >= dec!(0),
+                            "[Fee]: Invalid min or max"
+                        );
+                        if let (Some(min), Some(max)) = (min, max) {
+                            assert!(min <= max, "[Fee]: Min must be less than max");
+                        }
+                    }
+                }
+            }
+        }
     }
 }
@@ -1,29 +1,80 @@
 use crate::badge_manager::badge_manager::BadgeManager;
 use crate::marketplace::marketplace::Marketplace;
 use crate::member::member::Member;
+use crate::multisig::multisig::Multisig;
 use crate::types::*;
-use crate::vesting_schedule::VestingSchedule;
+use crate::vesting_schedule::{Severance, VestingCurve, VestingSchedule, VestingUnit};
 use scrypto::prelude::*;
 
 #[blueprint]
+#[events(
+    CreateEvent,
+    DetailsEvent,
+    DepositEvent,
+    ListEvent,
+    InviteEvent,
+    RemoveEvent,
+    LeaveEvent,
+    UpdateEvent,
+    JoinEvent,
+    RewardEvent,
+    WithdrawEvent,
+    CancellationEvent,
+    PauseEvent,
+    ResumeEvent,
+    DelistEvent,
+    ApplyEvent,
+    RejectEvent,
+    ProposeEvent,
+    ApproveEvent,
+    ExecuteEvent,
+    NoticeEvent
+)]
 mod job {
     enable_method_auth! {
         roles {
-            admin => updatable_by: [];
+            // updated by the component when the admin badge is recovered
+            admin => updatable_by: [SELF];
         },
         methods {
             init => PUBLIC;
             invite => restrict_to: [admin];
+            invite_member => restrict_to: [admin];
             remove => restrict_to: [admin, SELF];
             leave => PUBLIC;
             join => PUBLIC;
+            apply => PUBLIC;
+            accept_application => restrict_to: [admin];
+            reject_application => restrict_to: [admin];
+            clear_applications => restrict_to: [admin];
+            accept_member_application => restrict_to: [admin];
+            applications => PUBLIC;
             deposit => restrict_to: [admin];
+            signer_deposit => PUBLIC;
             details => restrict_to: [admin];
             withdraw => PUBLIC;
-            cancellation => restrict_to: [admin];
+            request_cancellation => restrict_to: [admin];
+            finalize_cancellation => restrict_to: [admin];
+            pause => restrict_to: [admin];
+            resume => restrict_to: [admin];
             list => restrict_to: [admin];
+            delist => PUBLIC;
             data => PUBLIC;
+            txs => PUBLIC;
+            txs_by_type => PUBLIC;
+            txs_by_badge => PUBLIC;
+            txs_total => PUBLIC;
+            tx_years => PUBLIC;
+            tx_year => PUBLIC;
+            member_data => PUBLIC;
             role => PUBLIC;
+            update_admin => PUBLIC;
+            set_multisig => restrict_to: [admin];
+            propose => PUBLIC;
+            approve => PUBLIC;
+            execute => PUBLIC;
+            proposal => PUBLIC;
+            signers => PUBLIC;
         }
     }
 
@@ -40,15 +91,23 @@ mod job {
         admin_badge: ResourceAddress,
         admin_handle: String,
         member_badges: HashMap<ResourceAddress, String>,
+        applications: HashMap<ResourceAddress, ApplicationData>,
         signatures: HashSet<ResourceAddress>,
         funds: FungibleVault,
         resource_address: ResourceAddress,
 
         vesting_schedule: VestingSchedule,
         reserved: FungibleVault,
+        is_multi: bool,
+        severance: Option<Severance>,
+        schedules: HashMap<ResourceAddress, VestingSchedule>,
+        reserves: HashMap<ResourceAddress, FungibleVault>,
         is_cancelled: bool,
         created_epoch: Decimal,
         list_epoch: Decimal,
+        notice_period: i64,
+        cancel_request_epoch: Option<Decimal>,
+        multisig: Option<Owned<Multisig>>,
     }
 
     impl Job {
@@ -65,7 +124,111 @@ mod job {
             cliff_epoch: Option<i64>,
             end_epoch: i64,
             vest_interval: i64,
+            vest_unit: VestingUnit,
+            vest_curve: VestingCurve,
             is_check_join: bool,
+            notice_period: i64,
+            severance: Option<Severance>,
+            image: String,
+            category: String,
+            details: HashMap<String, String>,
+        ) -> (Global<Job>, NonFungibleBucket) {
+            let vesting_schedule = VestingSchedule::new(
+                start_epoch,
+                cliff_epoch,
+                end_epoch,
+                vest_interval,
+                vest_unit,
+                vest_curve,
+                dec!(0),
+                is_check_join,
+            );
+
+            Self::create(
+                dapp_address,
+                member_address,
+                admin_badge,
+                admin_proof,
+                team_handle,
+                contract_handle,
+                contract_name,
+                resource_address,
+                vesting_schedule,
+                false,
+                notice_period,
+                severance,
+                image,
+                category,
+                details,
+            )
+        }
+
+        /// Creates a Job that pays multiple members from one funded vault
+        ///
+        /// Each member is given their own vesting schedule & reserved vault
+        /// through `invite_member`. The contract schedule only tracks the
+        /// total deposited amount & whether joining is checked.
+        ///
+        pub fn instantiate_multi(
+            dapp_address: ComponentAddress,
+            member_address: Option<ComponentAddress>,
+            admin_badge: ResourceAddress,
+            admin_proof: NonFungibleProof,
+            team_handle: String,
+            contract_handle: String,
+            contract_name: String,
+            resource_address: ResourceAddress,
+            is_check_join: bool,
+            notice_period: i64,
+            severance: Option<Severance>,
+            image: String,
+            category: String,
+            details: HashMap<String, String>,
+        ) -> (Global<Job>, NonFungibleBucket) {
+            let curr_epoch = VestingSchedule::get_curr_epoch();
+            let vesting_schedule = VestingSchedule::new(
+                curr_epoch,
+                None,
+                curr_epoch,
+                1i64,
+                VestingUnit::Days,
+                VestingCurve::Linear,
+                dec!(0),
+                is_check_join,
+            );
+
+            Self::create(
+                dapp_address,
+                member_address,
+                admin_badge,
+                admin_proof,
+                team_handle,
+                contract_handle,
+                contract_name,
+                resource_address,
+                vesting_schedule,
+                true,
+                notice_period,
+                severance,
+                image,
+                category,
+                details,
+            )
+        }
+
+        fn create(
+            dapp_address: ComponentAddress,
+            member_address: Option<ComponentAddress>,
+            admin_badge: ResourceAddress,
+            admin_proof: NonFungibleProof,
+            team_handle: String,
+            contract_handle: String,
+            contract_name: String,
+            resource_address: ResourceAddress,
+            vesting_schedule: VestingSchedule,
+            is_multi: bool,
+            notice_period: i64,
+            severance: Option<Severance>,
             image: String,
             category: String,
             details: HashMap<String, String>,
@@ -73,7 +236,12 @@ mod job {
             let (address_reservation, component_address) =
                 Runtime::allocate_component_address(Job::blueprint_id());
 
-            let admin_handle = Self::get_proof_id(&admin_badge, admin_proof);
+            let admin_handle = Self::get_member_handle(&admin_badge, admin_proof);
+            // a zero notice period would let a cancellation be requested & finalized in one tx
+            assert!(notice_period > 0, "[Instantiate]: Invalid notice period");
+            if let Some(Severance::Amount(amount)) = &severance {
+                assert!(amount >= &dec!(0), "[Instantiate]: Invalid severance");
+            }
             let badge_manager =
                 BadgeManager::new(component_address, ContractKind::Job, contract_name.clone());
             let new_details = KeyValueStore::<String, String>::new();
@@ -81,15 +249,6 @@ mod job {
                 new_details.insert(key.to_owned(), value.to_owned());
             }
 
-            let vesting_schedule = VestingSchedule::new(
-                start_epoch,
-                cliff_epoch,
-                end_epoch,
-                vest_interval,
-                dec!(0),
-                is_check_join,
-            );
-
             let component = Self {
                 badge_manager,
                 team_handle,
@@ -103,15 +262,23 @@ mod job {
                 admin_badge,
                 admin_handle,
                 member_badges: HashMap::new(),
+                applications: HashMap::new(),
                 signatures: HashSet::new(),
                 funds: FungibleVault::new(resource_address),
                 resource_address,
 
                 vesting_schedule,
                 reserved: FungibleVault::new(resource_address),
+                is_multi,
+                severance,
+                schedules: HashMap::new(),
+                reserves: HashMap::new(),
                 is_cancelled: false,
                 created_epoch: Decimal::from(VestingSchedule::get_curr_epoch()),
                 list_epoch: dec!(0),
+                notice_period,
+                cancel_request_epoch: None,
+                multisig: None,
             }
             .instantiate()
             .prepare_to_globalize(OwnerRole::None)
@@ -159,6 +326,7 @@ mod job {
                 self.admin_badge,
                 self.contract_handle.clone(),
                 self.badge_manager.badge(),
+                Some(self.resource_address),
                 self.funds.amount(),
                 TxType::Create,
             );
@@ -167,46 +335,69 @@ mod job {
         }
 
         pub fn invite(&mut self, member_badge: ResourceAddress, member_handle: String) {
-            assert!(self.member_badges.is_empty(), "[Invite]: Already Added");
-            let is_valid = ResourceManager::from(member_badge)
-                .non_fungible_exists(&BadgeManager::nft_id(member_handle.clone()));
-            assert!(is_valid, "[Invite]: Not valid");
-            self.member_badges
-                .insert(member_badge, member_handle.clone());
+            assert!(self.multisig.is_none(), "[Invite]: Requires proposal");
+            self.add_member(member_badge, member_handle);
+        }
 
-            // CREATE TXS
-            self.create_tx(
-                self.admin_handle.clone(),
-                self.admin_badge,
-                member_handle,
+        pub fn invite_member(
+            &mut self,
+            member_badge: ResourceAddress,
+            member_handle: String,
+            start_epoch: i64,
+            cliff_epoch: Option<i64>,
+            end_epoch: i64,
+            vest_interval: i64,
+            vest_unit: VestingUnit,
+            vest_curve: VestingCurve,
+            amount: Decimal,
+        ) {
+            assert!(
+                self.multisig.is_none(),
+                "[Invite Member]: Requires proposal"
+            );
+            self.add_schedule(
                 member_badge,
-                dec!(0),
-                TxType::Invite,
+                member_handle,
+                start_epoch,
+                cliff_epoch,
+                end_epoch,
+                vest_interval,
+                vest_unit,
+                vest_curve,
+                amount,
             );
         }
 
         pub fn remove(&mut self, member_badge: ResourceAddress) {
-            let is_signed = self.signatures.remove(&member_badge);
-            assert!(!is_signed, "[Remove]: Already Signed");
-            let handle = self.member_badges.remove(&member_badge).unwrap();
-            // CREATE TXS
-            self.create_tx(
-                self.admin_handle.clone(),
-                self.admin_badge,
-                handle,
-                member_badge,
-                dec!(0),
-                TxType::Remove,
-            );
+            assert!(self.multisig.is_none(), "[Remove]: Requires proposal");
+            self.remove_member(member_badge);
         }
 
         pub fn leave(&mut self, member_badge: ResourceAddress, proof: NonFungibleProof) {
             let member_handle = self.check_proof(&member_badge, proof);
 
             assert!(!self.is_cancelled, "[Leave]: Already Cancelled");
-            self.set_reserved();
-            assert!(self.reserved.amount() == dec!("0"), "[Leave]: Not zero");
-            self.set_cancelled();
+            if self.is_multi {
+                let schedule = self.schedules.get(&member_badge).unwrap();
+                assert!(schedule.cancel_epoch.is_none(), "[Leave]: Already Left");
+                // a signed member keeps their vested amount reserved to withdraw after leaving
+                self.set_member_reserved(&member_badge);
+                if !self.signatures.contains(&member_badge) {
+                    let reserved = self.reserves.get_mut(&member_badge).unwrap().take_all();
+                    self.funds.put(reserved);
+                }
+                let schedule = self.schedules.get_mut(&member_badge).unwrap();
+                schedule.cancel_epoch = Some(VestingSchedule::get_curr_epoch());
+            } else {
+                assert!(
+                    self.vesting_schedule.cancel_epoch.is_none(),
+                    "[Leave]: Already Left"
+                );
+                self.set_reserved();
+                assert!(self.reserved.amount() == dec!("0"), "[Leave]: Not zero");
+                // the admin can finalize the cancellation without waiting out the notice period
+                self.vesting_schedule.cancel_epoch = Some(VestingSchedule::get_curr_epoch());
+            }
 
             // CREATE TXS
             self.create_tx(
@@ -214,9 +405,109 @@ mod job {
                 member_badge,
                 self.contract_handle.clone(),
                 self.badge_manager.badge(),
+                None,
                 dec!(0),
                 TxType::Leave,
             );
+            self.update_listings();
+        }
+
+        pub fn apply(
+            &mut self,
+            member_badge: ResourceAddress,
+            proof: NonFungibleProof,
+            details: HashMap<String, String>,
+        ) {
+            let member_handle = Self::get_member_handle(&member_badge, proof);
+            assert!(self.is_joinable(), "[Apply]: Not joinable");
+            assert!(
+                !self.member_badges.contains_key(&member_badge),
+                "[Apply]: Already Added"
+            );
+            assert!(
+                !self.applications.contains_key(&member_badge),
+                "[Apply]: Already Applied"
+            );
+            assert!(
+                self.applications.len() < MAX_APPLICATIONS,
+                "[Apply]: Too many applications"
+            );
+            self.applications.insert(
+                member_badge,
+                ApplicationData {
+                    handle: member_handle.clone(),
+                    epoch: Decimal::from(VestingSchedule::get_curr_epoch()),
+                    details,
+                },
+            );
+
+            // CREATE TXS
+            self.create_tx(
+                member_handle,
+                member_badge,
+                self.contract_handle.clone(),
+                self.badge_manager.badge(),
+                None,
+                dec!(0),
+                TxType::Apply,
+            );
+        }
+
+        pub fn accept_application(&mut self, member_badge: ResourceAddress) {
+            assert!(
+                self.multisig.is_none(),
+                "[Accept Application]: Requires proposal"
+            );
+            self.accept_member(member_badge);
+        }
+
+        /// Accepts an application in multi mode with the member's vesting schedule
+        pub fn accept_member_application(
+            &mut self,
+            member_badge: ResourceAddress,
+            start_epoch: i64,
+            cliff_epoch: Option<i64>,
+            end_epoch: i64,
+            vest_interval: i64,
+            vest_unit: VestingUnit,
+            vest_curve: VestingCurve,
+            amount: Decimal,
+        ) {
+            assert!(
+                self.multisig.is_none(),
+                "[Accept Application]: Requires proposal"
+            );
+            self.accept_schedule(
+                member_badge,
+                start_epoch,
+                cliff_epoch,
+                end_epoch,
+                vest_interval,
+                vest_unit,
+                vest_curve,
+                amount,
+            );
+        }
+
+        pub fn reject_application(&mut self, member_badge: ResourceAddress) {
+            assert!(
+                self.multisig.is_none(),
+                "[Reject Application]: Requires proposal"
+            );
+            self.reject_member(member_badge);
+        }
+
+        /// Rejects every pending application
+        pub fn clear_applications(&mut self) {
+            assert!(
+                self.multisig.is_none(),
+                "[Clear Applications]: Requires proposal"
+            );
+            self.reject_members();
+        }
+
+        pub fn applications(&self) -> HashMap<ResourceAddress, ApplicationData> {
+            self.applications.clone()
         }
 
         pub fn join(
@@ -224,8 +515,13 @@ mod job {
             member_badge: ResourceAddress,
             proof: NonFungibleProof,
         ) -> NonFungibleBucket {
-            self.vesting_schedule.check_join();
+            assert!(!self.is_cancelled, "[Join]: Is Cancelled");
             let member_handle = self.check_proof(&member_badge, proof);
+            if self.is_multi {
+                self.schedules.get(&member_badge).unwrap().check_join();
+            } else {
+                self.vesting_schedule.check_join();
+            }
 
             assert!(!self.signatures.contains(&member_badge), "[Join]: Signed");
             self.signatures.insert(member_badge);
@@ -248,6 +544,7 @@ mod job {
                 member_badge,
                 self.contract_handle.clone(),
                 self.badge_manager.badge(),
+                None,
                 dec!(0),
                 TxType::Join,
             );
@@ -256,38 +553,24 @@ mod job {
         }
 
         pub fn deposit(&mut self, funds: FungibleBucket) {
-            assert!(!self.is_cancelled, "[Deposit]: Is Cancelled");
-            Self::check_funds(&funds);
-
-            // CREATE TXS
-            self.create_tx(
-                self.admin_handle.clone(),
-                self.admin_badge,
-                self.contract_handle.clone(),
-                self.badge_manager.badge(),
-                funds.amount(),
-                TxType::Deposit,
-            );
+            assert!(self.multisig.is_none(), "[Deposit]: Requires signer");
+            self.deposit_funds(self.admin_handle.clone(), self.admin_badge, funds);
+        }
 
-            self.vesting_schedule.amount = self.vesting_schedule.amount + funds.amount();
-            self.funds.put(funds);
+        /// Deposits can't be proposed, with a multisig any signer deposits instead of the admin
+        pub fn signer_deposit(
+            &mut self,
+            signer_badge: ResourceAddress,
+            proof: NonFungibleProof,
+            funds: FungibleBucket,
+        ) {
+            let signer_handle = self.check_signer(&signer_badge, proof);
+            self.deposit_funds(signer_handle, signer_badge, funds);
         }
 
         pub fn details(&mut self, image: String, details: HashMap<String, String>) {
-            self.image = Url::of(image);
-            for (key, value) in details.iter() {
-                self.details.insert(key.to_owned(), value.to_owned());
-            }
-
-            // CREATE TXS
-            self.create_tx(
-                self.admin_handle.clone(),
-                self.admin_badge,
-                self.contract_handle.clone(),
-                self.badge_manager.badge(),
-                dec!(0),
-                TxType::Details,
-            );
+            assert!(self.multisig.is_none(), "[Details]: Requires proposal");
+            self.set_details(image, details);
         }
 
         pub fn withdraw(
@@ -298,12 +581,25 @@ mod job {
             self.check_list();
             let member_handle = self.check_proof(&member_badge, proof);
 
-            assert!(!self.signatures.is_empty(), "[Withdraw]: Not Signed");
-            self.set_reserved();
-            let withdraw_bucket = self.reserved.take_all();
+            assert!(
+                self.signatures.contains(&member_badge),
+                "[Withdraw]: Not Signed"
+            );
+            let withdraw_bucket = if self.is_multi {
+                self.set_member_reserved(&member_badge);
+                self.reserves.get_mut(&member_badge).unwrap().take_all()
+            } else {
+                self.set_reserved();
+                self.reserved.take_all()
+            };
             let amount = withdraw_bucket.amount();
             assert!(amount > dec!("0"), "[Withdraw]: Must not be zero");
-            self.vesting_schedule.withdrawn = self.vesting_schedule.withdrawn + amount;
+            if self.is_multi {
+                let schedule = self.schedules.get_mut(&member_badge).unwrap();
+                schedule.withdrawn = schedule.withdrawn + amount;
+            } else {
+                self.vesting_schedule.withdrawn = self.vesting_schedule.withdrawn + amount;
+            }
 
             // CREATE TXS
             self.create_tx(
@@ -311,6 +607,7 @@ mod job {
                 self.badge_manager.badge(),
                 member_handle,
                 member_badge,
+                Some(self.resource_address),
                 amount,
                 TxType::Withdraw,
             );
@@ -318,76 +615,282 @@ mod job {
             withdraw_bucket
         }
 
-        pub fn cancellation(&mut self) -> FungibleBucket {
-            self.check_list();
+        /// Starts the notice period, vesting continues until it's finalized
+        pub fn request_cancellation(&mut self) {
+            assert!(self.multisig.is_none(), "[Cancellation]: Requires proposal");
+            self.request_cancel();
+        }
 
-            if !self.signatures.is_empty() {
-                self.set_reserved();
-            }
-            if !self.is_cancelled {
-                self.set_cancelled();
-            }
-            let total = self.funds.take_all();
+        pub fn finalize_cancellation(&mut self) -> FungibleBucket {
+            assert!(self.multisig.is_none(), "[Cancellation]: Requires proposal");
+            self.finalize_cancel()
+        }
 
-            // CREATE TXS
-            self.create_tx(
-                self.contract_handle.clone(),
-                self.badge_manager.badge(),
-                self.admin_handle.clone(),
-                self.admin_badge,
-                total.amount(),
-                TxType::Cancellation,
-            );
+        pub fn pause(&mut self, member_badge: ResourceAddress) {
+            assert!(self.multisig.is_none(), "[Pause]: Requires proposal");
+            self.pause_member(member_badge);
+        }
 
-            total
+        pub fn resume(&mut self, member_badge: ResourceAddress) {
+            assert!(self.multisig.is_none(), "[Resume]: Requires proposal");
+            self.resume_member(member_badge);
         }
 
         pub fn list(&mut self, marketplace_address: ComponentAddress) {
-            let marketplace = Global::<Marketplace>::from(marketplace_address);
-            marketplace.check_contract(
-                self.category.clone(),
-                ContractKind::Job,
-                self.vesting_schedule.amount,
-                self.funds.resource_address(),
-            );
-            assert!(self.marketplaces.is_empty(), "[List]: Already added");
-            self.marketplaces.insert(marketplace_address);
+            assert!(self.multisig.is_none(), "[List]: Requires proposal");
+            self.add_marketplace(marketplace_address);
+        }
 
-            self.list_epoch = Decimal::from(VestingSchedule::get_curr_epoch());
+        /// Called by a listed marketplace after removing the contract from its category
+        pub fn delist(&mut self, marketplace_address: ComponentAddress) {
+            assert!(
+                self.marketplaces.contains(&marketplace_address),
+                "[Delist]: Not listed"
+            );
+            Runtime::assert_access_rule(rule!(require(global_caller(marketplace_address))));
+            self.marketplaces.remove(&marketplace_address);
 
             // CREATE TXS
             self.create_tx(
-                self.admin_handle.clone(),
-                self.admin_badge,
                 self.contract_handle.clone(),
                 self.badge_manager.badge(),
+                self.admin_handle.clone(),
+                self.admin_badge,
+                None,
                 dec!(0),
-                TxType::List,
+                TxType::Delist,
             );
         }
 
-        pub fn data(
-            &self,
-        ) -> (
-            HashSet<ComponentAddress>,
-            String,
-            ResourceAddress,
+        /// Requires the replacement id once the admin badge is recovered, the lost badge loses the admin role
+        pub fn update_admin(&mut self) {
+            let badge_id: Option<String> = ResourceManager::from(self.admin_badge)
+                .get_metadata(BADGE_ID)
+                .unwrap_or(None);
+            let badge_id = badge_id.expect("[Update Admin]: Not recovered");
+            let badge_id =
+                NonFungibleLocalId::String(StringNonFungibleLocalId::new(&badge_id).unwrap());
+            Runtime::global_component().set_role(
+                "admin",
+                rule!(require(NonFungibleGlobalId::new(
+                    self.admin_badge,
+                    badge_id
+                ))),
+            );
+        }
+
+        /// Once set, the JobAction methods must be proposed & approved by the signers
+        pub fn set_multisig(&mut self, signers: Vec<ResourceAddress>, threshold: u64) {
+            assert!(
+                self.multisig.is_none(),
+                "[Multisig]: Already set, propose SetSigners to rotate"
+            );
+            self.get_admin_member();
+            self.multisig = Some(Multisig::new(signers, threshold));
+        }
+
+        pub fn propose(
+            &mut self,
+            signer_badge: ResourceAddress,
+            proof: NonFungibleProof,
+            action: JobAction,
+        ) -> Decimal {
+            let signer_handle = Self::get_member_handle(&signer_badge, proof);
+            let proposal_id = self
+                .get_multisig()
+                .propose(signer_badge, AdminAction::Job(action));
+
+            // CREATE TXS
+            self.create_proposal_tx(signer_handle, signer_badge, proposal_id, TxType::Propose);
+
+            proposal_id
+        }
+
+        pub fn approve(
+            &mut self,
+            signer_badge: ResourceAddress,
+            proof: NonFungibleProof,
+            proposal_id: Decimal,
+        ) {
+            let signer_handle = Self::get_member_handle(&signer_badge, proof);
+            self.get_multisig().approve(signer_badge, proposal_id);
+
+            // CREATE TXS
+            self.create_proposal_tx(signer_handle, signer_badge, proposal_id, TxType::Approve);
+        }
+
+        /// Any signer can execute, the bucket of a FinalizeCancellation goes to the admin's Member
+        pub fn execute(
+            &mut self,
+            signer_badge: ResourceAddress,
+            proof: NonFungibleProof,
+            proposal_id: Decimal,
+        ) {
+            let signer_handle = Self::get_member_handle(&signer_badge, proof);
+            let action = match self.get_multisig().execute(signer_badge, proposal_id) {
+                AdminAction::Job(action) => action,
+                AdminAction::Project(_) => {
+                    Runtime::panic(String::from("[Execute]: Invalid action"))
+                }
+            };
+
+            // CREATE TXS
+            self.create_proposal_tx(signer_handle, signer_badge, proposal_id, TxType::Execute);
+
+            match action {
+                JobAction::Invite(member_badge, member_handle) => {
+                    self.add_member(member_badge, member_handle)
+                }
+                JobAction::InviteMember(
+                    member_badge,
+                    member_handle,
+                    start_epoch,
+                    cliff_epoch,
+                    end_epoch,
+                    vest_interval,
+                    vest_unit,
+                    vest_curve,
+                    amount,
+                ) => self.add_schedule(
+                    member_badge,
+                    member_handle,
+                    start_epoch,
+                    cliff_epoch,
+                    end_epoch,
+                    vest_interval,
+                    vest_unit,
+                    vest_curve,
+                    amount,
+                ),
+                JobAction::AcceptApplication(member_badge) => self.accept_member(member_badge),
+                JobAction::AcceptMemberApplication(
+                    member_badge,
+                    start_epoch,
+                    cliff_epoch,
+                    end_epoch,
+                    vest_interval,
+                    vest_unit,
+                    vest_curve,
+                    amount,
+                ) => self.accept_schedule(
+                    member_badge,
+                    start_epoch,
+                    cliff_epoch,
+                    end_epoch,
+                    vest_interval,
+                    vest_unit,
+                    vest_curve,
+                    amount,
+                ),
+                JobAction::RejectApplication(member_badge) => self.reject_member(member_badge),
+                JobAction::ClearApplications => self.reject_members(),
+                JobAction::Pause(member_badge) => self.pause_member(member_badge),
+                JobAction::Resume(member_badge) => self.resume_member(member_badge),
+                JobAction::Details(image, details) => self.set_details(image, details),
+                JobAction::Remove(member_badge) => self.remove_member(member_badge),
+                JobAction::List(marketplace_address) => self.add_marketplace(marketplace_address),
+                JobAction::Cancellation => self.request_cancel(),
+                JobAction::FinalizeCancellation => {
+                    let bucket = self.finalize_cancel();
+                    self.get_admin_member()
+                        .receive_funds(vec![Bucket::from(bucket)]);
+                }
+                JobAction::SetSigners(signers, threshold) => {
+                    self.get_multisig().set_signers(signers, threshold)
+                }
+            }
+        }
+
+        pub fn proposal(&self, proposal_id: Decimal) -> Option<ProposalData> {
+            self.get_multisig().get_proposal(proposal_id)
+        }
+
+        pub fn signers(&self) -> Option<(HashSet<ResourceAddress>, u64)> {
+            self.multisig
+                .as_ref()
+                .map(|multisig| multisig.get_signers())
+        }
+
+        pub fn data(
+            &self,
+        ) -> (
+            HashSet<ComponentAddress>,
+            String,
+            ResourceAddress,
             Decimal,
             ResourceAddress,
             bool,
+            bool,
             ComponentAddress,
+            i64,
+            Option<Decimal>,
         ) {
             (
                 self.marketplaces.clone(),
                 self.category.clone(),
                 self.admin_badge,
-                self.vesting_schedule.amount,
+                self.get_listed_amount(),
                 self.funds.resource_address(),
-                self.member_badges.is_empty() && !self.is_cancelled,
+                self.is_joinable(),
+                self.is_cancelled,
                 Runtime::global_address(),
+                self.notice_period,
+                self.cancel_request_epoch,
+            )
+        }
+
+        /// Returns the schedule of a member: amount, vested, withdrawn, reserved, end, is signed.
+        /// The vested amount includes any severance paid on cancellation
+        pub fn member_data(
+            &self,
+            member_badge: ResourceAddress,
+        ) -> (Decimal, Decimal, Decimal, Decimal, i64, bool) {
+            assert!(
+                self.member_badges.contains_key(&member_badge),
+                "[Member Data]: No Member"
+            );
+            let (schedule, reserved) = if self.is_multi {
+                (
+                    self.schedules.get(&member_badge).unwrap(),
+                    self.reserves.get(&member_badge).unwrap(),
+                )
+            } else {
+                (&self.vesting_schedule, &self.reserved)
+            };
+            (
+                schedule.amount,
+                schedule.get_owed(),
+                schedule.withdrawn,
+                reserved.amount(),
+                schedule.get_end_epoch(),
+                self.signatures.contains(&member_badge),
             )
         }
 
+        pub fn txs(&self, start: u64, end: u64) -> Vec<TxData> {
+            self.badge_manager.get_txs(start, end, None, None)
+        }
+
+        pub fn txs_by_type(&self, tx_type: TxType, start: u64, end: u64) -> Vec<TxData> {
+            self.badge_manager.get_txs(start, end, Some(tx_type), None)
+        }
+
+        pub fn txs_by_badge(&self, badge: ResourceAddress, start: u64, end: u64) -> Vec<TxData> {
+            self.badge_manager.get_txs(start, end, None, Some(badge))
+        }
+
+        pub fn txs_total(&self) -> Decimal {
+            self.badge_manager.get_total()
+        }
+
+        pub fn tx_years(&self) -> Vec<Decimal> {
+            self.badge_manager.get_years()
+        }
+
+        pub fn tx_year(&self, year: Decimal) -> Option<YearData> {
+            self.badge_manager.get_year_data(year)
+        }
+
         pub fn role(&self, member_badge: ResourceAddress) -> ContractRole {
             let is_admin = self.admin_badge == member_badge;
             let is_member = self.signatures.contains(&member_badge);
@@ -402,6 +905,413 @@ mod job {
 
         // Private Funcs
 
+        fn add_member(&mut self, member_badge: ResourceAddress, member_handle: String) {
+            assert!(!self.is_multi, "[Invite]: Use invite_member");
+            assert!(self.member_badges.is_empty(), "[Invite]: Already Added");
+            let is_valid = ResourceManager::from(member_badge)
+                .non_fungible_exists(&BadgeManager::nft_id(member_handle.clone()));
+            assert!(is_valid, "[Invite]: Not valid");
+            self.member_badges
+                .insert(member_badge, member_handle.clone());
+            self.applications.remove(&member_badge);
+
+            // CREATE TXS
+            self.create_tx(
+                self.admin_handle.clone(),
+                self.admin_badge,
+                member_handle,
+                member_badge,
+                None,
+                dec!(0),
+                TxType::Invite,
+            );
+            self.update_listings();
+        }
+
+        fn add_schedule(
+            &mut self,
+            member_badge: ResourceAddress,
+            member_handle: String,
+            start_epoch: i64,
+            cliff_epoch: Option<i64>,
+            end_epoch: i64,
+            vest_interval: i64,
+            vest_unit: VestingUnit,
+            vest_curve: VestingCurve,
+            amount: Decimal,
+        ) {
+            assert!(self.is_multi, "[Invite Member]: Use invite");
+            assert!(!self.is_cancelled, "[Invite Member]: Is Cancelled");
+            assert!(
+                self.member_badges.len() < MAX_MEMBERS,
+                "[Invite Member]: Too many members"
+            );
+            assert!(
+                !self.member_badges.contains_key(&member_badge),
+                "[Invite Member]: Already Added"
+            );
+            let is_valid = ResourceManager::from(member_badge)
+                .non_fungible_exists(&BadgeManager::nft_id(member_handle.clone()));
+            assert!(is_valid, "[Invite Member]: Not valid");
+            assert!(amount > dec!(0), "[Invite Member]: No Amount");
+            assert!(
+                amount <= self.get_unallocated(),
+                "[Invite Member]: Not enough funds"
+            );
+
+            let schedule = VestingSchedule::new(
+                start_epoch,
+                cliff_epoch,
+                end_epoch,
+                vest_interval,
+                vest_unit,
+                vest_curve,
+                amount,
+                self.vesting_schedule.is_check_join,
+            );
+            self.member_badges
+                .insert(member_badge, member_handle.clone());
+            self.applications.remove(&member_badge);
+            self.schedules.insert(member_badge, schedule);
+            // a removed member keeps their empty vault, vaults can't be dropped
+            let resource_address = self.resource_address;
+            self.reserves
+                .entry(member_badge)
+                .or_insert_with(|| FungibleVault::new(resource_address));
+
+            // CREATE TXS
+            self.create_tx(
+                self.admin_handle.clone(),
+                self.admin_badge,
+                member_handle,
+                member_badge,
+                Some(self.resource_address),
+                amount,
+                TxType::Invite,
+            );
+            self.update_listings();
+        }
+
+        fn accept_member(&mut self, member_badge: ResourceAddress) {
+            assert!(
+                !self.is_multi,
+                "[Accept Application]: Use accept_member_application"
+            );
+            assert!(self.is_joinable(), "[Accept Application]: Not joinable");
+            let application = self.applications.remove(&member_badge).unwrap();
+            self.add_member(member_badge, application.handle);
+        }
+
+        fn accept_schedule(
+            &mut self,
+            member_badge: ResourceAddress,
+            start_epoch: i64,
+            cliff_epoch: Option<i64>,
+            end_epoch: i64,
+            vest_interval: i64,
+            vest_unit: VestingUnit,
+            vest_curve: VestingCurve,
+            amount: Decimal,
+        ) {
+            assert!(self.is_joinable(), "[Accept Application]: Not joinable");
+            let application = self.applications.remove(&member_badge).unwrap();
+            self.add_schedule(
+                member_badge,
+                application.handle,
+                start_epoch,
+                cliff_epoch,
+                end_epoch,
+                vest_interval,
+                vest_unit,
+                vest_curve,
+                amount,
+            );
+        }
+
+        fn reject_member(&mut self, member_badge: ResourceAddress) {
+            let application = self.applications.remove(&member_badge).unwrap();
+
+            // CREATE TXS
+            self.create_tx(
+                self.admin_handle.clone(),
+                self.admin_badge,
+                application.handle,
+                member_badge,
+                None,
+                dec!(0),
+                TxType::Reject,
+            );
+        }
+
+        fn reject_members(&mut self) {
+            let member_badges: Vec<ResourceAddress> = self.applications.keys().cloned().collect();
+            for member_badge in member_badges {
+                self.reject_member(member_badge);
+            }
+        }
+
+        fn deposit_funds(
+            &mut self,
+            sender_handle: String,
+            sender_badge: ResourceAddress,
+            funds: FungibleBucket,
+        ) {
+            assert!(!self.is_cancelled, "[Deposit]: Is Cancelled");
+            Self::check_funds(&funds);
+
+            // CREATE TXS
+            self.create_tx(
+                sender_handle,
+                sender_badge,
+                self.contract_handle.clone(),
+                self.badge_manager.badge(),
+                Some(self.resource_address),
+                funds.amount(),
+                TxType::Deposit,
+            );
+
+            self.vesting_schedule.amount = self.vesting_schedule.amount + funds.amount();
+            self.funds.put(funds);
+        }
+
+        fn add_marketplace(&mut self, marketplace_address: ComponentAddress) {
+            let marketplace = Global::<Marketplace>::from(marketplace_address);
+            marketplace.check_contract(
+                self.category.clone(),
+                ContractKind::Job,
+                self.get_listed_amount(),
+                self.funds.resource_address(),
+            );
+            assert!(self.marketplaces.is_empty(), "[List]: Already added");
+            self.marketplaces.insert(marketplace_address);
+
+            self.list_epoch = Decimal::from(VestingSchedule::get_curr_epoch());
+
+            // CREATE TXS
+            self.create_tx(
+                self.admin_handle.clone(),
+                self.admin_badge,
+                self.contract_handle.clone(),
+                self.badge_manager.badge(),
+                None,
+                dec!(0),
+                TxType::List,
+            );
+        }
+
+        fn check_signer(&self, signer_badge: &ResourceAddress, proof: NonFungibleProof) -> String {
+            let signer_handle = Self::get_member_handle(signer_badge, proof);
+            let (signers, _) = self.get_multisig().get_signers();
+            assert!(signers.contains(signer_badge), "[Multisig]: Not a signer");
+            signer_handle
+        }
+
+        /// Funds of executed proposals go to the admin's Member instead of the signer
+        fn get_admin_member(&self) -> Global<Member> {
+            let member_address: Option<GlobalAddress> = ResourceManager::from(self.admin_badge)
+                .get_metadata(MEMBER_ADDRESS)
+                .unwrap_or(None);
+            let member_address = member_address.expect("[Multisig]: Admin has no Member");
+            Global::<Member>::from(ComponentAddress::try_from(member_address).unwrap())
+        }
+
+        fn cancel(&mut self) -> FungibleBucket {
+            self.check_list();
+
+            if self.is_multi {
+                let members: Vec<ResourceAddress> = self.schedules.keys().cloned().collect();
+                let mut severances: Vec<(ResourceAddress, Decimal)> = Vec::new();
+                for member_badge in members.iter() {
+                    if self.signatures.contains(member_badge) {
+                        self.set_member_reserved(member_badge);
+                    }
+                    let schedule = self.schedules.get_mut(member_badge).unwrap();
+                    // members that left aren't owed severance
+                    if schedule.cancel_epoch.is_none() {
+                        schedule.cancel_epoch = Some(VestingSchedule::get_curr_epoch());
+                        if self.signatures.contains(member_badge) {
+                            let schedule = self.schedules.get(member_badge).unwrap();
+                            severances.push((*member_badge, self.get_severance(schedule)));
+                        }
+                    }
+                }
+                // every vested amount is reserved before severance is taken, each severance is
+                // capped at the member's own unvested share & a shortfall is split pro rata
+                let total = severances
+                    .iter()
+                    .fold(dec!(0), |total, (_, severance)| total + *severance);
+                let funds = self.funds.amount();
+                for (member_badge, severance) in severances {
+                    let severance = if total > funds {
+                        severance * funds / total
+                    } else {
+                        severance
+                    };
+                    let severance_bucket = self
+                        .funds
+                        .take_advanced(severance, WithdrawStrategy::Rounded(RoundingMode::ToZero));
+                    self.schedules.get_mut(&member_badge).unwrap().severance =
+                        severance_bucket.amount();
+                    self.reserves
+                        .get_mut(&member_badge)
+                        .unwrap()
+                        .put(severance_bucket);
+                }
+            } else {
+                if !self.signatures.is_empty() {
+                    self.set_reserved();
+                }
+                // a member that left isn't owed severance
+                if self.vesting_schedule.cancel_epoch.is_none() {
+                    self.vesting_schedule.cancel_epoch = Some(VestingSchedule::get_curr_epoch());
+                    if !self.signatures.is_empty() {
+                        let severance = self.get_severance(&self.vesting_schedule);
+                        let severance_bucket = self.funds.take_advanced(
+                            severance,
+                            WithdrawStrategy::Rounded(RoundingMode::ToZero),
+                        );
+                        self.vesting_schedule.severance = severance_bucket.amount();
+                        self.reserved.put(severance_bucket);
+                    }
+                }
+            }
+            self.is_cancelled = true;
+            let total = self.funds.take_all();
+
+            // CREATE TXS
+            self.create_tx(
+                self.contract_handle.clone(),
+                self.badge_manager.badge(),
+                self.admin_handle.clone(),
+                self.admin_badge,
+                Some(self.resource_address),
+                total.amount(),
+                TxType::Cancellation,
+            );
+            self.update_listings();
+
+            total
+        }
+
+        fn pause_member(&mut self, member_badge: ResourceAddress) {
+            assert!(!self.is_cancelled, "[Pause]: Is Cancelled");
+            self.get_schedule_mut(&member_badge).pause();
+
+            // CREATE TXS
+            let handle = self.member_badges.get(&member_badge).unwrap().to_owned();
+            self.create_tx(
+                self.admin_handle.clone(),
+                self.admin_badge,
+                handle,
+                member_badge,
+                None,
+                dec!(0),
+                TxType::Pause,
+            );
+        }
+
+        fn resume_member(&mut self, member_badge: ResourceAddress) {
+            assert!(!self.is_cancelled, "[Resume]: Is Cancelled");
+            self.get_schedule_mut(&member_badge).resume();
+
+            // CREATE TXS
+            let handle = self.member_badges.get(&member_badge).unwrap().to_owned();
+            self.create_tx(
+                self.admin_handle.clone(),
+                self.admin_badge,
+                handle,
+                member_badge,
+                None,
+                dec!(0),
+                TxType::Resume,
+            );
+        }
+
+        fn remove_member(&mut self, member_badge: ResourceAddress) {
+            let is_signed = self.signatures.remove(&member_badge);
+            assert!(!is_signed, "[Remove]: Already Signed");
+            let handle = self.member_badges.remove(&member_badge).unwrap();
+            // unvested amount of an unsigned member returns to the unallocated funds
+            if self.is_multi {
+                self.schedules.remove(&member_badge);
+                let reserved = self.reserves.get_mut(&member_badge).unwrap().take_all();
+                self.funds.put(reserved);
+            }
+            // CREATE TXS
+            self.create_tx(
+                self.admin_handle.clone(),
+                self.admin_badge,
+                handle,
+                member_badge,
+                None,
+                dec!(0),
+                TxType::Remove,
+            );
+            self.update_listings();
+        }
+
+        fn set_details(&mut self, image: String, details: HashMap<String, String>) {
+            self.image = Url::of(image);
+            for (key, value) in details.iter() {
+                self.details.insert(key.to_owned(), value.to_owned());
+            }
+
+            // CREATE TXS
+            self.create_tx(
+                self.admin_handle.clone(),
+                self.admin_badge,
+                self.contract_handle.clone(),
+                self.badge_manager.badge(),
+                None,
+                dec!(0),
+                TxType::Details,
+            );
+        }
+
+        fn finalize_cancel(&mut self) -> FungibleBucket {
+            assert!(!self.is_cancelled, "[Cancellation]: Already Cancelled");
+            // the member of a single Job already left, so there's no one to give notice to
+            if !self.is_left() {
+                assert!(
+                    self.cancel_request_epoch.is_some(),
+                    "[Cancellation]: Not requested"
+                );
+                assert!(
+                    Decimal::from(VestingSchedule::get_curr_epoch())
+                        >= self.cancel_request_epoch.unwrap() + self.notice_period,
+                    "[Cancellation]: Notice period not over"
+                );
+            }
+            self.cancel()
+        }
+
+        fn request_cancel(&mut self) {
+            self.check_list();
+            assert!(!self.is_cancelled, "[Cancellation]: Already Cancelled");
+            assert!(
+                self.cancel_request_epoch.is_none(),
+                "[Cancellation]: Already requested"
+            );
+            self.cancel_request_epoch = Some(Decimal::from(VestingSchedule::get_curr_epoch()));
+
+            // CREATE TXS
+            self.create_tx(
+                self.admin_handle.clone(),
+                self.admin_badge,
+                self.contract_handle.clone(),
+                self.badge_manager.badge(),
+                None,
+                dec!(0),
+                TxType::Notice,
+            );
+            self.update_listings();
+        }
+
+        fn get_multisig(&self) -> &Owned<Multisig> {
+            assert!(self.multisig.is_some(), "[Multisig]: Not set");
+            self.multisig.as_ref().unwrap()
+        }
+
         fn set_reserved(&mut self) {
             if self.is_cancelled || self.vesting_schedule.amount == dec!(0) {
                 return;
@@ -421,10 +1331,98 @@ mod job {
             }
         }
 
-        fn set_cancelled(&mut self) {
-            let cancel_epoch = VestingSchedule::get_curr_epoch();
-            self.is_cancelled = true;
-            self.vesting_schedule.cancel_epoch = Some(cancel_epoch);
+        fn set_member_reserved(&mut self, member_badge: &ResourceAddress) {
+            let schedule = self.schedules.get(member_badge).unwrap();
+            let reserved = self.reserves.get_mut(member_badge).unwrap();
+            // vested tokens not yet withdrawn or reserved are moved out of the shared funds
+            let reserve_amount = schedule.get_owed() - schedule.withdrawn - reserved.amount();
+            if reserve_amount > dec!(0) {
+                let reserve_bucket = self.funds.take_advanced(
+                    reserve_amount,
+                    WithdrawStrategy::Rounded(RoundingMode::ToZero),
+                );
+                reserved.put(reserve_bucket);
+            }
+        }
+
+        fn get_severance(&self, schedule: &VestingSchedule) -> Decimal {
+            let severance = match &self.severance {
+                Some(severance) => schedule.get_severance(severance),
+                None => dec!(0),
+            };
+            severance.min(self.funds.amount())
+        }
+
+        fn get_schedule_mut(&mut self, member_badge: &ResourceAddress) -> &mut VestingSchedule {
+            assert!(
+                self.member_badges.contains_key(member_badge),
+                "[Schedule]: No Member"
+            );
+            if self.is_multi {
+                self.schedules.get_mut(member_badge).unwrap()
+            } else {
+                &mut self.vesting_schedule
+            }
+        }
+
+        fn get_unallocated(&self) -> Decimal {
+            let mut allocated = dec!(0);
+            for (member_badge, schedule) in self.schedules.iter() {
+                let reserved = self.reserves.get(member_badge).unwrap();
+                // a cancelled schedule is owed its vested amount & severance
+                let total = match schedule.cancel_epoch {
+                    None => schedule.amount,
+                    Some(_) if self.signatures.contains(member_badge) => schedule.get_owed(),
+                    // an unsigned member can't withdraw, cancellation sweeps their vested amount
+                    Some(_) => schedule.withdrawn + reserved.amount(),
+                };
+                allocated = allocated + total - schedule.withdrawn - reserved.amount();
+            }
+            self.funds.amount() - allocated
+        }
+
+        /// A single Job lists its deposited amount, a multi Job its unallocated funds
+        fn get_listed_amount(&self) -> Decimal {
+            if self.is_multi {
+                self.get_unallocated()
+            } else {
+                self.vesting_schedule.amount
+            }
+        }
+
+        fn update_listings(&self) {
+            let state = if self.is_cancelled || self.is_left() {
+                ListingState::Closed
+            } else if !self.is_joinable() {
+                ListingState::Filled
+            } else {
+                ListingState::Active
+            };
+            for marketplace_address in self.marketplaces.iter() {
+                let marketplace = Global::<Marketplace>::from(*marketplace_address);
+                marketplace.update_listing(
+                    Runtime::global_address(),
+                    self.category.clone(),
+                    ContractKind::Job,
+                    state.clone(),
+                );
+            }
+        }
+
+        fn is_joinable(&self) -> bool {
+            if self.cancel_request_epoch.is_some() {
+                return false;
+            }
+            if self.is_multi {
+                self.member_badges.len() < MAX_MEMBERS && !self.is_cancelled
+            } else {
+                self.member_badges.is_empty() && !self.is_cancelled
+            }
+        }
+
+        /// The member of a single Job left, nothing more vests for them
+        fn is_left(&self) -> bool {
+            !self.is_multi && self.vesting_schedule.cancel_epoch.is_some()
         }
 
         fn check_list(&self) {
@@ -441,6 +1439,7 @@ mod job {
             from_badge: ResourceAddress,
             to_handle: String,
             to_badge: ResourceAddress,
+            resource_address: Option<ResourceAddress>,
             amount: Decimal,
             tx_type: TxType,
         ) {
@@ -452,14 +1451,57 @@ mod job {
                 to_handle,
                 to_badge,
 
+                resource_address,
                 amount,
+                ids: IndexSet::new(),
+                proposal_id: None,
                 tx_type,
             };
+            emit_tx_event(Runtime::global_address(), &tx_data);
             self.badge_manager.create_tx(tx_data);
         }
 
+        fn create_proposal_tx(
+            &self,
+            signer_handle: String,
+            signer_badge: ResourceAddress,
+            proposal_id: Decimal,
+            tx_type: TxType,
+        ) {
+            let tx_data = TxData {
+                epoch: Decimal::from(VestingSchedule::get_curr_epoch()),
+                from_handle: signer_handle,
+                from_badge: signer_badge,
+                to_handle: self.contract_handle.clone(),
+                to_badge: self.badge_manager.badge(),
+                resource_address: None,
+                amount: dec!(0),
+                ids: IndexSet::new(),
+                proposal_id: Some(proposal_id),
+                tx_type,
+            };
+            emit_tx_event(Runtime::global_address(), &tx_data);
+            self.badge_manager.create_tx(tx_data);
+        }
+
+        /// Accepts a proof of the Member badge or of the non-transferable member nft of this contract
         fn check_proof(&self, member_badge: &ResourceAddress, proof: NonFungibleProof) -> String {
-            let handle = Self::get_proof_id(member_badge, proof);
+            let contract_badge = self.badge_manager.member_badge();
+            if proof.resource_address() == contract_badge {
+                let handle = Self::get_proof_id(&contract_badge, proof);
+                // handles aren't unique across Member badges, the nft id can only be minted once
+                // so it's bound to the signed member that joined with it
+                let joined_badge = self
+                    .signatures
+                    .iter()
+                    .find(|badge| self.member_badges.get(*badge) == Some(&handle));
+                assert!(
+                    joined_badge == Some(member_badge),
+                    "[Check Proof]: Not Equal"
+                );
+                return handle;
+            }
+            let handle = Self::get_member_handle(member_badge, proof);
             let saved_handle = self.member_badges.get(&member_badge).unwrap();
             assert!(&handle == saved_handle, "[Check Proof]: Not Equal");
             handle
@@ -474,6 +1516,23 @@ mod job {
             string_id.value().to_owned()
         }
 
+        /// Member badges are resolved by their metadata, so a recovered badge keeps the handle
+        fn get_member_handle(badge: &ResourceAddress, proof: NonFungibleProof) -> String {
+            let manager = ResourceManager::from(*badge);
+            let handle: Option<String> = manager.get_metadata(MEMBER_HANDLE).unwrap_or(None);
+            let badge_id: Option<String> = manager.get_metadata(BADGE_ID).unwrap_or(None);
+            let id = Self::get_proof_id(badge, proof);
+            match handle {
+                // the id of a lost badge is no longer valid once replaced
+                Some(handle) => {
+                    let valid_id = badge_id.unwrap_or(handle.clone());
+                    assert!(id == valid_id, "[Check Proof]: Replaced badge");
+                    handle
+                }
+                None => id,
+            }
+        }
+
         fn check_funds(funds: &FungibleBucket) {
             let resource = ResourceManager::from_address(funds.resource_address()).resource_type();
             assert!(
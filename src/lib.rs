@@ -1,10 +1,12 @@
 mod badge_manager;
 mod category;
 mod list;
-mod types;
-mod vesting_schedule;
+mod multisig;
+mod treasury;
 
 pub mod job;
 pub mod marketplace;
 pub mod member;
 pub mod project;
+pub mod types;
+pub mod vesting_schedule;
@@ -1,3 +1,4 @@
+use crate::types::MAX_PAGE;
 use scrypto::prelude::*;
 
 #[blueprint]
@@ -6,7 +7,10 @@ mod list {
     struct List {
         all: KeyValueStore<ComponentAddress, String>,
         list: KeyValueStore<String, Option<ComponentAddress>>,
+        // every added address by key, including removed ones
+        addresses: KeyValueStore<String, ComponentAddress>,
         list_total: Decimal,
+        removed_total: Decimal,
     }
 
     impl List {
@@ -14,7 +18,9 @@ mod list {
             Self {
                 all: KeyValueStore::new(),
                 list: KeyValueStore::new(),
+                addresses: KeyValueStore::new(),
                 list_total: dec!(0),
+                removed_total: dec!(0),
             }
             .instantiate()
         }
@@ -27,12 +33,74 @@ mod list {
             self.list_total = new_total;
             let key = format!("{new_total}");
             self.list.insert(key.clone(), Some(address));
+            self.addresses.insert(key.clone(), address);
             self.all.insert(address, key);
         }
 
         pub fn remove(&mut self, address: ComponentAddress) {
-            let key = self.all.get(&address).unwrap();
-            self.list.insert(key.clone(), None);
+            let key = self
+                .all
+                .get(&address)
+                .expect("[Delist]: Not listed")
+                .clone();
+            let is_active = self.list.get(&key).unwrap().is_some();
+            if is_active {
+                self.list.insert(key, None);
+                self.removed_total = self.removed_total + 1;
+            }
+        }
+
+        pub fn restore(&mut self, address: ComponentAddress) {
+            let key = self.all.get(&address).unwrap().clone();
+            let is_removed = self.list.get(&key).unwrap().is_none();
+            if is_removed {
+                self.list.insert(key, Some(address));
+                self.removed_total = self.removed_total - 1;
+            }
+        }
+
+        /// Removes the addresses within the index range & returns them, active or not
+        pub fn take_page(&mut self, start: u64, end: u64) -> Vec<ComponentAddress> {
+            assert!(start > 0 && end >= start, "[List]: Invalid range");
+            assert!(end - start <= MAX_PAGE, "[List]: Range too large");
+
+            let mut addresses = Vec::new();
+            for index in start..end {
+                let address = match self.addresses.get(&format!("{index}")) {
+                    Some(address) => *address,
+                    None => break,
+                };
+                self.remove(address);
+                addresses.push(address);
+            }
+            addresses
+        }
+
+        pub fn contains(&self, address: ComponentAddress) -> bool {
+            self.all.get(&address).is_some()
+        }
+
+        /// Returns the active addresses within the index range, skipping removed ones
+        pub fn get_page(&self, start: u64, end: u64) -> Vec<ComponentAddress> {
+            assert!(start > 0 && end >= start, "[List]: Invalid range");
+            assert!(end - start <= MAX_PAGE, "[List]: Range too large");
+
+            let mut addresses = Vec::new();
+            for index in start..end {
+                let address = match self.list.get(&format!("{index}")) {
+                    Some(address) => *address,
+                    None => break,
+                };
+                if let Some(address) = address {
+                    addresses.push(address);
+                }
+            }
+            addresses
+        }
+
+        /// Returns the number of active & removed addresses
+        pub fn get_counts(&self) -> (Decimal, Decimal) {
+            (self.list_total - self.removed_total, self.removed_total)
         }
     }
 }
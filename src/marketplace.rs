@@ -1,6 +1,8 @@
 use crate::category::category::Category;
 use crate::job::job::Job;
+use crate::member::member::Member;
 use crate::project::project::Project;
+use crate::treasury::treasury::Treasury;
 use crate::types::*;
 use scrypto::prelude::*;
 
@@ -14,21 +16,51 @@ mod marketplace {
             update => restrict_to: [admin];
             add_category => restrict_to: [admin];
             update_category => restrict_to: [admin];
+            rename_category => restrict_to: [admin];
+            remove_category => restrict_to: [admin];
+            set_arbiter => restrict_to: [admin];
+            set_curator => restrict_to: [admin];
+            set_fee_shares => restrict_to: [admin];
+            migrate_contracts => restrict_to: [admin];
             remove_contract => restrict_to: [admin];
             deposit => restrict_to: [admin, SELF];
             withdraw => restrict_to: [admin, SELF];
+            withdraw_amount => restrict_to: [admin, SELF];
+            withdraw_ids => restrict_to: [admin, SELF];
+            balances => PUBLIC;
             check_contract => PUBLIC;
+            categories => PUBLIC;
+            contracts => PUBLIC;
+            contract_counts => PUBLIC;
+            contract_state => PUBLIC;
+            update_listing => PUBLIC;
+            arbiter => PUBLIC;
+            fee_shares => PUBLIC;
+            earnings => PUBLIC;
+            claim => PUBLIC;
             add_project => PUBLIC;
             add_job => PUBLIC;
+            delist_project => PUBLIC;
+            delist_job => PUBLIC;
         }
     }
 
     struct Marketplace {
         admin_badge: ResourceAddress,
         name: String,
+        arbiter_badge: Option<ResourceAddress>,
+        referrer_percent: Decimal,
+        curator_percent: Decimal,
         projects: KeyValueStore<String, Owned<Category>>,
         jobs: KeyValueStore<String, Owned<Category>>,
-        resources: KeyValueStore<ResourceAddress, Vault>,
+        project_categories: Vec<String>,
+        job_categories: Vec<String>,
+        project_aliases: KeyValueStore<String, String>,
+        job_aliases: KeyValueStore<String, String>,
+        treasury: Owned<Treasury>,
+        // unclaimed referrer & curator fee shares
+        shares: KeyValueStore<ResourceAddress, Vault>,
+        earnings: KeyValueStore<ResourceAddress, HashMap<ResourceAddress, Decimal>>,
         details: KeyValueStore<String, String>,
     }
 
@@ -39,18 +71,18 @@ mod marketplace {
             dapp_address: ComponentAddress,
             categories: Vec<String>,
             minimum: Decimal,
-            fee: Decimal,
+            fees: HashMap<ResourceAddress, ListingFee>,
             resource_address: ResourceAddress,
         ) -> Global<Marketplace> {
             let projects = KeyValueStore::<String, Owned<Category>>::new();
             let jobs = KeyValueStore::<String, Owned<Category>>::new();
 
-            for category in categories {
+            for category in categories.iter().cloned() {
                 let all_projects = Category::new(
                     category.clone(),
                     ContractKind::Project,
                     minimum,
-                    fee,
+                    fees.clone(),
                     resource_address,
                 );
                 projects.insert(category.clone(), all_projects);
@@ -59,7 +91,7 @@ mod marketplace {
                     category.clone(),
                     ContractKind::Job,
                     minimum,
-                    fee,
+                    fees.clone(),
                     resource_address,
                 );
                 jobs.insert(category, all_jobs);
@@ -68,9 +100,18 @@ mod marketplace {
             let component = Self {
                 admin_badge,
                 name,
+                arbiter_badge: None,
+                referrer_percent: dec!(0),
+                curator_percent: dec!(0),
                 projects,
                 jobs,
-                resources: KeyValueStore::new(),
+                project_categories: categories.clone(),
+                job_categories: categories,
+                project_aliases: KeyValueStore::new(),
+                job_aliases: KeyValueStore::new(),
+                treasury: Treasury::new(),
+                shares: KeyValueStore::new(),
+                earnings: KeyValueStore::new(),
                 details: KeyValueStore::new(),
             }
             .instantiate()
@@ -98,30 +139,108 @@ mod marketplace {
             }
         }
 
+        pub fn set_arbiter(&mut self, arbiter_badge: Option<ResourceAddress>) {
+            self.arbiter_badge = arbiter_badge;
+        }
+
+        pub fn arbiter(&self) -> Option<ResourceAddress> {
+            self.arbiter_badge
+        }
+
+        /// Percentages of each listing fee paid to the referrer & category curator
+        pub fn set_fee_shares(&mut self, referrer_percent: Decimal, curator_percent: Decimal) {
+            assert!(
+                referrer_percent >= dec!(0)
+                    && curator_percent >= dec!(0)
+                    && referrer_percent + curator_percent <= dec!(100),
+                "[Fee Shares]: Invalid percent"
+            );
+            self.referrer_percent = referrer_percent;
+            self.curator_percent = curator_percent;
+        }
+
+        pub fn fee_shares(&self) -> (Decimal, Decimal) {
+            (self.referrer_percent, self.curator_percent)
+        }
+
+        pub fn set_curator(
+            &mut self,
+            name: String,
+            is_project: bool,
+            curator_badge: Option<ResourceAddress>,
+        ) {
+            let kind = if is_project {
+                ContractKind::Project
+            } else {
+                ContractKind::Job
+            };
+            let name = self.resolve_category(name, kind);
+            if is_project {
+                self.projects.get(&name).unwrap().set_curator(curator_badge);
+            } else {
+                self.jobs.get(&name).unwrap().set_curator(curator_badge);
+            }
+        }
+
+        /// Returns the unclaimed fee shares of a referrer or curator
+        pub fn earnings(&self, badge: ResourceAddress) -> HashMap<ResourceAddress, Decimal> {
+            match self.earnings.get(&badge) {
+                Some(earnings) => (*earnings).clone(),
+                None => HashMap::new(),
+            }
+        }
+
+        pub fn claim(&mut self, badge: ResourceAddress, proof: NonFungibleProof) -> Vec<Bucket> {
+            proof.check(badge);
+            let earnings = self.earnings.remove(&badge);
+            assert!(earnings.is_some(), "[Claim]: Nothing to claim");
+
+            let mut buckets = Vec::new();
+            for (resource_address, amount) in earnings.unwrap() {
+                let bucket = self.shares.get_mut(&resource_address).unwrap().take(amount);
+                buckets.push(bucket);
+            }
+            buckets
+        }
+
+        /// Adds the categories for a single contract kind, or both when no kind is given
         pub fn add_category(
             &mut self,
             names: Vec<String>,
+            kind: Option<ContractKind>,
             minimum: Decimal,
-            fee: Decimal,
+            fees: HashMap<ResourceAddress, ListingFee>,
             resource_address: ResourceAddress,
         ) {
+            let kinds = match kind {
+                Some(kind) => vec![kind],
+                None => vec![ContractKind::Project, ContractKind::Job],
+            };
             for name in names {
-                let category = Category::new(
-                    name.clone(),
-                    ContractKind::Project,
-                    minimum,
-                    fee,
-                    resource_address,
-                );
-                self.projects.insert(name.clone(), category);
-                let category = Category::new(
-                    name.clone(),
-                    ContractKind::Job,
-                    minimum,
-                    fee,
-                    resource_address,
-                );
-                self.jobs.insert(name, category);
+                for kind in kinds.iter().cloned() {
+                    let (categories, aliases, category_names) = if kind == ContractKind::Project {
+                        (
+                            &mut self.projects,
+                            &mut self.project_aliases,
+                            &mut self.project_categories,
+                        )
+                    } else {
+                        (
+                            &mut self.jobs,
+                            &mut self.job_aliases,
+                            &mut self.job_categories,
+                        )
+                    };
+                    assert!(
+                        categories.get(&name).is_none(),
+                        "[Add Category]: Already added"
+                    );
+                    assert!(aliases.get(&name).is_none(), "[Add Category]: Name in use");
+                    let category =
+                        Category::new(name.clone(), kind, minimum, fees.clone(), resource_address);
+                    categories.insert(name.clone(), category);
+                    category_names.push(name.clone());
+                }
             }
         }
 
@@ -130,15 +249,177 @@ mod marketplace {
             name: String,
             is_project: bool,
             minimum: Decimal,
+            fees: HashMap<ResourceAddress, ListingFee>,
+            resource_address: ResourceAddress,
+            is_active: bool,
             details: HashMap<String, String>,
         ) {
-            if is_project {
-                self.projects
-                    .get(&name)
-                    .unwrap()
-                    .update(name, minimum, details);
+            assert!(
+                self.categories(is_project).contains(&name),
+                "[Update Category]: Category not found"
+            );
+            let categories = if is_project {
+                &self.projects
+            } else {
+                &self.jobs
+            };
+            categories.get(&name).unwrap().update(
+                minimum,
+                fees,
+                resource_address,
+                is_active,
+                details,
+            );
+        }
+
+        /// Moves the category to a new key, listed contracts keep resolving via the old name
+        pub fn rename_category(&mut self, name: String, new_name: String, is_project: bool) {
+            assert!(
+                self.categories(is_project).contains(&name),
+                "[Rename Category]: Category not found"
+            );
+            let (categories, aliases, names) = if is_project {
+                (
+                    &mut self.projects,
+                    &mut self.project_aliases,
+                    &mut self.project_categories,
+                )
             } else {
-                self.jobs.get(&name).unwrap().update(name, minimum, details);
+                (
+                    &mut self.jobs,
+                    &mut self.job_aliases,
+                    &mut self.job_categories,
+                )
+            };
+            assert!(
+                categories.get(&new_name).is_none() && aliases.get(&new_name).is_none(),
+                "[Rename Category]: Name in use"
+            );
+
+            let category = categories.remove(&name).unwrap();
+            category.rename(new_name.clone());
+            categories.insert(new_name.clone(), category);
+            aliases.insert(name.clone(), new_name.clone());
+            for category_name in names.iter_mut() {
+                if *category_name == name {
+                    *category_name = new_name.clone();
+                }
+            }
+        }
+
+        /// Retires a category, listings are then moved to `migrate_to` with `migrate_contracts`
+        pub fn remove_category(
+            &mut self,
+            name: String,
+            is_project: bool,
+            migrate_to: Option<String>,
+        ) {
+            assert!(
+                self.categories(is_project).contains(&name),
+                "[Remove Category]: Category not found"
+            );
+            let (categories, aliases, names) = if is_project {
+                (
+                    &mut self.projects,
+                    &mut self.project_aliases,
+                    &mut self.project_categories,
+                )
+            } else {
+                (
+                    &mut self.jobs,
+                    &mut self.job_aliases,
+                    &mut self.job_categories,
+                )
+            };
+
+            let category = categories.get(&name).unwrap();
+            match migrate_to {
+                Some(migrate_to) => {
+                    assert!(
+                        migrate_to != name && names.contains(&migrate_to),
+                        "[Remove Category]: Invalid migration category"
+                    );
+                    aliases.insert(name.clone(), migrate_to);
+                }
+                None => {
+                    let (active, _removed) = category.get_counts();
+                    assert!(active == dec!(0), "[Remove Category]: Has active listings");
+                }
+            }
+            category.retire();
+            names.retain(|category_name| *category_name != name);
+        }
+
+        /// Moves a page of listings from a removed category to the category it was migrated to
+        ///
+        /// Listings keep their current state, contracts that don't meet the new category's
+        /// resource or minimum are delisted. Pages can be re-run
+        ///
+        pub fn migrate_contracts(&mut self, name: String, is_project: bool, start: u64, end: u64) {
+            let kind = if is_project {
+                ContractKind::Project
+            } else {
+                ContractKind::Job
+            };
+            let migrate_to = self.resolve_category(name.clone(), kind);
+            assert!(migrate_to != name, "[Migrate Contracts]: Not migrated");
+
+            let contracts = if is_project {
+                self.projects.get(&name).unwrap().take_contracts(start, end)
+            } else {
+                self.jobs.get(&name).unwrap().take_contracts(start, end)
+            };
+            let categories = if is_project {
+                &self.projects
+            } else {
+                &self.jobs
+            };
+            // a page that was already migrated is skipped, so a re-run doesn't fail
+            let contracts: Vec<ComponentAddress> = contracts
+                .into_iter()
+                .filter(|address| !categories.get(&migrate_to).unwrap().contains(*address))
+                .collect();
+            for contract_address in contracts {
+                let (marketplaces, amount, resource_address, is_joinable, is_cancelled) =
+                    if is_project {
+                        let (marketplaces, _, _, amount, resource, is_joinable, is_cancelled, ..) =
+                            Global::<Project>::from(contract_address).data();
+                        (marketplaces, amount, resource, is_joinable, is_cancelled)
+                    } else {
+                        let (marketplaces, _, _, amount, resource, is_joinable, is_cancelled, ..) =
+                            Global::<Job>::from(contract_address).data();
+                        (marketplaces, amount, resource, is_joinable, is_cancelled)
+                    };
+                // delisted contracts stay removed
+                if !marketplaces.contains(&Runtime::global_address()) {
+                    continue;
+                }
+                let state = if is_cancelled {
+                    ListingState::Closed
+                } else if !is_joinable {
+                    ListingState::Filled
+                } else {
+                    ListingState::Active
+                };
+
+                let categories = if is_project {
+                    &self.projects
+                } else {
+                    &self.jobs
+                };
+                let is_migrated = categories.get(&migrate_to).unwrap().migrate(
+                    contract_address,
+                    state,
+                    amount,
+                    resource_address,
+                );
+                if !is_migrated {
+                    if is_project {
+                        Global::<Project>::from(contract_address).delist(Runtime::global_address());
+                    } else {
+                        Global::<Job>::from(contract_address).delist(Runtime::global_address());
+                    }
+                }
             }
         }
 
@@ -148,32 +429,48 @@ mod marketplace {
             name: String,
             is_project: bool,
         ) {
+            let kind = if is_project {
+                ContractKind::Project
+            } else {
+                ContractKind::Job
+            };
+            let name = self.resolve_category(name, kind);
+            // delisting stops the contract from restoring its listing on later updates
             if is_project {
                 self.projects.get(&name).unwrap().remove(component_address);
+                Global::<Project>::from(component_address).delist(Runtime::global_address());
             } else {
                 self.jobs.get(&name).unwrap().remove(component_address);
+                Global::<Job>::from(component_address).delist(Runtime::global_address());
             }
         }
 
         pub fn deposit(&mut self, bucket: Bucket) {
-            let resource_address = bucket.resource_address();
-            let has_resource = self.resources.get(&resource_address).is_some();
-            if has_resource {
-                self.resources
-                    .get_mut(&resource_address)
-                    .unwrap()
-                    .put(bucket);
-            } else {
-                self.resources
-                    .insert(resource_address, Vault::with_bucket(bucket));
-            }
+            self.treasury.deposit(bucket);
         }
 
         pub fn withdraw(&mut self, resource_address: ResourceAddress) -> Bucket {
-            self.resources
-                .get_mut(&resource_address)
-                .unwrap()
-                .take_all()
+            self.treasury.withdraw(resource_address)
+        }
+
+        pub fn withdraw_amount(
+            &mut self,
+            resource_address: ResourceAddress,
+            amount: Decimal,
+        ) -> Bucket {
+            self.treasury.withdraw_amount(resource_address, amount)
+        }
+
+        pub fn withdraw_ids(
+            &mut self,
+            resource_address: ResourceAddress,
+            ids: IndexSet<NonFungibleLocalId>,
+        ) -> Bucket {
+            self.treasury.withdraw_ids(resource_address, ids)
+        }
+
+        pub fn balances(&self) -> Balances {
+            self.treasury.balances()
         }
 
         pub fn check_contract(
@@ -183,6 +480,7 @@ mod marketplace {
             contract_amount: Decimal,
             contract_resource: ResourceAddress,
         ) {
+            let name = self.resolve_category(name, kind.clone());
             if kind == ContractKind::Project {
                 self.projects
                     .get(&name)
@@ -196,12 +494,87 @@ mod marketplace {
             }
         }
 
+        pub fn categories(&self, is_project: bool) -> Vec<String> {
+            if is_project {
+                self.project_categories.clone()
+            } else {
+                self.job_categories.clone()
+            }
+        }
+
+        pub fn contracts(
+            &self,
+            name: String,
+            is_project: bool,
+            start: u64,
+            end: u64,
+        ) -> Vec<ComponentAddress> {
+            if is_project {
+                let name = self.resolve_category(name, ContractKind::Project);
+                self.projects.get(&name).unwrap().get_contracts(start, end)
+            } else {
+                let name = self.resolve_category(name, ContractKind::Job);
+                self.jobs.get(&name).unwrap().get_contracts(start, end)
+            }
+        }
+
+        /// Returns the number of active & removed contracts in a category
+        pub fn contract_counts(&self, name: String, is_project: bool) -> (Decimal, Decimal) {
+            if is_project {
+                let name = self.resolve_category(name, ContractKind::Project);
+                self.projects.get(&name).unwrap().get_counts()
+            } else {
+                let name = self.resolve_category(name, ContractKind::Job);
+                self.jobs.get(&name).unwrap().get_counts()
+            }
+        }
+
+        pub fn contract_state(
+            &self,
+            name: String,
+            is_project: bool,
+            contract_address: ComponentAddress,
+        ) -> Option<ListingState> {
+            if is_project {
+                let name = self.resolve_category(name, ContractKind::Project);
+                self.projects
+                    .get(&name)
+                    .unwrap()
+                    .get_state(contract_address)
+            } else {
+                let name = self.resolve_category(name, ContractKind::Job);
+                self.jobs.get(&name).unwrap().get_state(contract_address)
+            }
+        }
+
+        /// Called by a listed contract when it's filled, reopened or closed
+        pub fn update_listing(
+            &mut self,
+            contract_address: ComponentAddress,
+            name: String,
+            kind: ContractKind,
+            state: ListingState,
+        ) {
+            Runtime::assert_access_rule(rule!(require(global_caller(contract_address))));
+            let name = self.resolve_category(name, kind.clone());
+            let categories = if kind == ContractKind::Project {
+                &self.projects
+            } else {
+                &self.jobs
+            };
+            if let Some(category) = categories.get(&name) {
+                category.set_state(contract_address, state);
+            }
+        }
+
+        /// Returns the change of a fee bucket paying more than the category fee
         pub fn add_project(
             &mut self,
             project_address: ComponentAddress,
             proof: NonFungibleProof,
-            fee_bucket: FungibleBucket,
-        ) {
+            mut fee_bucket: FungibleBucket,
+            referrer_badge: Option<ResourceAddress>,
+        ) -> FungibleBucket {
             let project = Global::<Project>::from(project_address);
             let (
                 marketplaces,
@@ -210,36 +583,43 @@ mod marketplace {
                 contract_amount,
                 contract_resource,
                 is_joinable,
+                _is_cancelled,
                 contract_address,
+                _notice_period,
+                _cancel_request_epoch,
             ) = project.data();
-            proof.check(admin_badge);
+            Self::check_admin(admin_badge, proof);
             assert!(
                 marketplaces.contains(&Runtime::global_address()),
                 "[Add Project]: Marketplace addresses must be the same"
             );
             assert!(is_joinable, "[Add Project]: Not joinable");
+            let category = self.resolve_category(category, ContractKind::Project);
             let category = self.projects.get(&category).unwrap();
 
-            let category_fee = category.check_contract(contract_amount, contract_resource);
+            category.check_contract(contract_amount, contract_resource);
+            let category_fee = category.get_fee(contract_amount, fee_bucket.resource_address());
             assert!(
-                fee_bucket.amount() == category_fee,
+                fee_bucket.amount() >= category_fee,
                 "[Add Project]: Missing fee"
             );
-            assert!(
-                fee_bucket.resource_address() == XRD,
-                "[Add Project]: Fee must be XRD"
-            );
             category.list(contract_address);
+            let curator_badge = category.get_curator();
             drop(category);
-            self.deposit(Bucket::from(fee_bucket));
+            Self::check_referrer(referrer_badge, admin_badge);
+            let fee = fee_bucket.take(category_fee);
+            self.split_fee(fee, referrer_badge, curator_badge);
+            fee_bucket
         }
 
+        /// Returns the change of a fee bucket paying more than the category fee
         pub fn add_job(
             &mut self,
             job_address: ComponentAddress,
             proof: NonFungibleProof,
-            fee_bucket: FungibleBucket,
-        ) {
+            mut fee_bucket: FungibleBucket,
+            referrer_badge: Option<ResourceAddress>,
+        ) -> FungibleBucket {
             let job = Global::<Job>::from(job_address);
             let (
                 marketplaces,
@@ -248,28 +628,207 @@ mod marketplace {
                 contract_amount,
                 contract_resource,
                 is_joinable,
+                _is_cancelled,
                 contract_address,
+                _notice_period,
+                _cancel_request_epoch,
             ) = job.data();
-            proof.check(admin_badge);
+            Self::check_admin(admin_badge, proof);
             assert!(
                 marketplaces.contains(&Runtime::global_address()),
                 "[Add Job]: Marketplace addresses must be the same"
             );
             assert!(is_joinable, "[Add Job]: Not joinable");
+            let category = self.resolve_category(category, ContractKind::Job);
             let category = self.jobs.get(&category).unwrap();
 
-            let category_fee = category.check_contract(contract_amount, contract_resource);
+            category.check_contract(contract_amount, contract_resource);
+            let category_fee = category.get_fee(contract_amount, fee_bucket.resource_address());
             assert!(
-                fee_bucket.amount() == category_fee,
+                fee_bucket.amount() >= category_fee,
                 "[Add Job]: Missing fee"
             );
-            assert!(
-                fee_bucket.resource_address() == XRD,
-                "[Add job]: Fee must be XRD"
-            );
             category.list(contract_address);
+            let curator_badge = category.get_curator();
             drop(category);
+            Self::check_referrer(referrer_badge, admin_badge);
+            let fee = fee_bucket.take(category_fee);
+            self.split_fee(fee, referrer_badge, curator_badge);
+            fee_bucket
+        }
+
+        pub fn delist_project(
+            &mut self,
+            project_address: ComponentAddress,
+            proof: Option<NonFungibleProof>,
+        ) {
+            let project = Global::<Project>::from(project_address);
+            let (
+                marketplaces,
+                category,
+                admin_badge,
+                _,
+                _,
+                _,
+                is_cancelled,
+                contract_address,
+                _,
+                _,
+            ) = project.data();
+            // cancelled contracts can be delisted by anyone
+            match proof {
+                Some(proof) => {
+                    Self::check_admin(admin_badge, proof);
+                }
+                None => assert!(is_cancelled, "[Delist Project]: Missing proof"),
+            }
+            assert!(
+                marketplaces.contains(&Runtime::global_address()),
+                "[Delist Project]: Not listed"
+            );
+            let category = self.resolve_category(category, ContractKind::Project);
+            self.projects
+                .get(&category)
+                .unwrap()
+                .remove(contract_address);
+            project.delist(Runtime::global_address());
+        }
+
+        pub fn delist_job(
+            &mut self,
+            job_address: ComponentAddress,
+            proof: Option<NonFungibleProof>,
+        ) {
+            let job = Global::<Job>::from(job_address);
+            let (
+                marketplaces,
+                category,
+                admin_badge,
+                _,
+                _,
+                _,
+                is_cancelled,
+                contract_address,
+                _,
+                _,
+            ) = job.data();
+            // cancelled contracts can be delisted by anyone
+            match proof {
+                Some(proof) => {
+                    Self::check_admin(admin_badge, proof);
+                }
+                None => assert!(is_cancelled, "[Delist Job]: Missing proof"),
+            }
+            assert!(
+                marketplaces.contains(&Runtime::global_address()),
+                "[Delist Job]: Not listed"
+            );
+            let category = self.resolve_category(category, ContractKind::Job);
+            self.jobs.get(&category).unwrap().remove(contract_address);
+            job.delist(Runtime::global_address());
+        }
+
+        /// A recovered admin badge replaces the id of the lost one in the badge metadata
+        fn check_admin(admin_badge: ResourceAddress, proof: NonFungibleProof) {
+            let result = proof.check(admin_badge);
+            let badge_id: Option<String> = ResourceManager::from(admin_badge)
+                .get_metadata(BADGE_ID)
+                .unwrap_or(None);
+            if let Some(badge_id) = badge_id {
+                assert!(
+                    result.non_fungible_local_id()
+                        == NonFungibleLocalId::String(
+                            StringNonFungibleLocalId::new(&badge_id).unwrap()
+                        ),
+                    "[Check Admin]: Replaced badge"
+                );
+            }
+        }
+
+        /// Referrers must be another member, known by the Member component in the badge metadata
+        fn check_referrer(referrer_badge: Option<ResourceAddress>, admin_badge: ResourceAddress) {
+            let referrer_badge = match referrer_badge {
+                Some(referrer_badge) => referrer_badge,
+                None => return,
+            };
+            assert!(
+                referrer_badge != admin_badge,
+                "[Referrer]: Can't refer your own contract"
+            );
+            let member_address: Option<GlobalAddress> = ResourceManager::from(referrer_badge)
+                .get_metadata(MEMBER_ADDRESS)
+                .unwrap_or(None);
+            assert!(member_address.is_some(), "[Referrer]: Not a member");
+            let member_address = ComponentAddress::try_from(member_address.unwrap()).unwrap();
+            assert!(
+                Global::<Member>::from(member_address).get_badge() == referrer_badge,
+                "[Referrer]: Not a member"
+            );
+        }
+
+        /// Moves the referrer & curator shares out of the fee, the remainder goes to the marketplace
+        fn split_fee(
+            &mut self,
+            mut fee_bucket: FungibleBucket,
+            referrer_badge: Option<ResourceAddress>,
+            curator_badge: Option<ResourceAddress>,
+        ) {
+            let resource_address = fee_bucket.resource_address();
+            let fee = fee_bucket.amount();
+            let beneficiaries = [
+                (referrer_badge, self.referrer_percent),
+                (curator_badge, self.curator_percent),
+            ];
+            for (badge, percent) in beneficiaries {
+                let share = fee * percent / dec!(100);
+                if badge.is_none() || share == dec!(0) {
+                    continue;
+                }
+                let share_bucket = fee_bucket
+                    .take_advanced(share, WithdrawStrategy::Rounded(RoundingMode::ToZero));
+                let share = share_bucket.amount();
+                let has_resource = self.shares.get(&resource_address).is_some();
+                if has_resource {
+                    self.shares
+                        .get_mut(&resource_address)
+                        .unwrap()
+                        .put(Bucket::from(share_bucket));
+                } else {
+                    self.shares.insert(
+                        resource_address,
+                        Vault::with_bucket(Bucket::from(share_bucket)),
+                    );
+                }
+
+                let badge = badge.unwrap();
+                let has_earnings = self.earnings.get(&badge).is_some();
+                if has_earnings {
+                    let mut earnings = self.earnings.get_mut(&badge).unwrap();
+                    let earned = earnings.entry(resource_address).or_insert(dec!(0));
+                    *earned = *earned + share;
+                } else {
+                    self.earnings
+                        .insert(badge, HashMap::from([(resource_address, share)]));
+                }
+            }
             self.deposit(Bucket::from(fee_bucket));
         }
+
+        /// Follows renamed & migrated categories to their current key
+        fn resolve_category(&self, name: String, kind: ContractKind) -> String {
+            let aliases = if kind == ContractKind::Project {
+                &self.project_aliases
+            } else {
+                &self.job_aliases
+            };
+            let mut name = name;
+            loop {
+                let alias = aliases.get(&name).map(|alias| (*alias).clone());
+                match alias {
+                    Some(alias) => name = alias,
+                    None => return name,
+                }
+            }
+        }
     }
 }
@@ -1,6 +1,7 @@
 use crate::job::job::Job;
 use crate::list::list::List;
 use crate::project::project::Project;
+use crate::treasury::treasury::Treasury;
 use crate::types::*;
 use scrypto::prelude::*;
 
@@ -9,19 +10,35 @@ use scrypto::prelude::*;
 mod member {
     enable_method_auth! {
         roles {
-            admin => updatable_by: [];
+            // updated by the component when a lost badge is recovered
+            admin => updatable_by: [SELF];
         },
         methods {
             add_project => PUBLIC;
             add_job => PUBLIC;
             remove_contract => restrict_to: [admin];
+            set_custody => restrict_to: [admin];
+            join_project => restrict_to: [admin];
+            join_job => restrict_to: [admin];
+            withdraw_from_project => restrict_to: [admin];
+            withdraw_from_job => restrict_to: [admin];
             deposit => restrict_to: [admin];
+            deposit_badges => restrict_to: [admin];
+            receive_funds => PUBLIC;
             withdraw => restrict_to: [admin];
+            withdraw_amount => restrict_to: [admin];
+            withdraw_ids => restrict_to: [admin];
+            balances => PUBLIC;
             update_members => restrict_to: [admin];
             update_team => restrict_to: [admin];
             remove_team => restrict_to: [admin];
             details => restrict_to: [admin];
             get_badge => PUBLIC;
+            set_guardians => restrict_to: [admin];
+            cancel_recovery => restrict_to: [admin];
+            approve_recovery => PUBLIC;
+            recover => PUBLIC;
+            recovery => PUBLIC;
         }
     }
 
@@ -29,6 +46,10 @@ mod member {
         admin_badge: ResourceAddress,
         badge_manager: ResourceManager,
         member_handle: String,
+        // replacement badges minted by guardians
+        recoveries: u64,
+        // keeps contract badges in the treasury instead of returning them
+        is_custodial: bool,
 
         project_admins: Owned<List>,
         project_members: Owned<List>,
@@ -37,8 +58,14 @@ mod member {
 
         member_badges: KeyValueStore<ResourceAddress, ()>,
         member_components: KeyValueStore<ComponentAddress, ()>,
+        // member badge issued by each joined contract
+        contract_badges: KeyValueStore<ComponentAddress, ResourceAddress>,
         teams: KeyValueStore<String, TeamData>,
-        resources: KeyValueStore<ResourceAddress, Vault>,
+        guardians: HashSet<ResourceAddress>,
+        recovery_threshold: u64,
+        recovery_delay: i64,
+        recovery: Option<RecoveryData>,
+        treasury: Owned<Treasury>,
         details: KeyValueStore<String, String>,
     }
 
@@ -70,7 +97,9 @@ mod member {
             let component = Self {
                 admin_badge,
                 badge_manager: badge_bucket.resource_manager(),
+                recoveries: 0,
                 member_handle,
+                is_custodial: false,
 
                 project_admins: List::new(),
                 project_members: List::new(),
@@ -79,8 +108,13 @@ mod member {
 
                 member_badges: KeyValueStore::new(),
                 member_components: KeyValueStore::new(),
+                contract_badges: KeyValueStore::new(),
                 teams: KeyValueStore::<String, TeamData>::new_with_registered_type(),
-                resources: KeyValueStore::new(),
+                guardians: HashSet::new(),
+                recovery_threshold: 0,
+                recovery_delay: 0,
+                recovery: None,
+                treasury: Treasury::new(),
                 details: KeyValueStore::new(),
             }
             .instantiate()
@@ -143,25 +177,123 @@ mod member {
             }
         }
 
+        pub fn set_custody(&mut self, is_custodial: bool) {
+            self.is_custodial = is_custodial;
+        }
+
+        /// The member badge proof is forwarded to the contract, the contract badge is kept if custodial
+        pub fn join_project(
+            &mut self,
+            project_address: ComponentAddress,
+            proof: NonFungibleProof,
+        ) -> Option<Bucket> {
+            let project = Global::<Project>::from(project_address);
+            let badge_bucket = project.join(self.admin_badge, proof);
+            if !self.project_members.contains(project_address) {
+                self.project_members.add(project_address);
+            }
+            self.keep_badge(project_address, badge_bucket)
+        }
+
+        pub fn join_job(
+            &mut self,
+            job_address: ComponentAddress,
+            proof: NonFungibleProof,
+        ) -> Option<Bucket> {
+            let job = Global::<Job>::from(job_address);
+            let badge_bucket = job.join(self.admin_badge, proof);
+            if !self.job_members.contains(job_address) {
+                self.job_members.add(job_address);
+            }
+            self.keep_badge(job_address, badge_bucket)
+        }
+
+        /// The contract badge kept in custody proves the withdrawal
+        pub fn withdraw_from_project(&mut self, project_address: ComponentAddress) {
+            let proof = self.contract_proof(project_address);
+            let project = Global::<Project>::from(project_address);
+            let buckets = project.withdraw(self.admin_badge, proof);
+            for bucket in buckets {
+                self.deposit(bucket);
+            }
+        }
+
+        pub fn withdraw_from_job(&mut self, job_address: ComponentAddress) {
+            let proof = self.contract_proof(job_address);
+            let job = Global::<Job>::from(job_address);
+            let bucket = job.withdraw(self.admin_badge, proof);
+            self.deposit(Bucket::from(bucket));
+        }
+
         pub fn deposit(&mut self, bucket: Bucket) {
-            let resource_address = bucket.resource_address();
-            let has_resource = self.resources.get(&resource_address).is_some();
-            if has_resource {
-                self.resources
-                    .get_mut(&resource_address)
-                    .unwrap()
-                    .put(bucket);
-            } else {
-                self.resources
-                    .insert(resource_address, Vault::with_bucket(bucket));
+            self.treasury.deposit(bucket);
+        }
+
+        /// Funds of proposals executed by a multisig Project or Job are kept in the treasury
+        pub fn receive_funds(&mut self, buckets: Vec<Bucket>) {
+            let package_address = Runtime::package_address();
+            Runtime::assert_access_rule(rule!(
+                require(global_caller(BlueprintId::new(&package_address, "Project")))
+                    || require(global_caller(BlueprintId::new(&package_address, "Job")))
+            ));
+            for bucket in buckets {
+                self.deposit(bucket);
+            }
+        }
+
+        /// Contract badges can't be withdrawn from an account,
+        /// a badge returned by a join is put in custody within the same transaction
+        pub fn deposit_badges(&mut self, badges: Vec<Bucket>) {
+            assert!(self.is_custodial, "[Deposit Badges]: Not custodial");
+            for badge_bucket in badges {
+                let badge_bucket = badge_bucket.as_non_fungible();
+                let badge = badge_bucket.non_fungible::<BadgeData>();
+                assert!(
+                    *badge.local_id()
+                        == NonFungibleLocalId::String(
+                            StringNonFungibleLocalId::new(&self.member_handle).unwrap()
+                        ),
+                    "[Deposit Badges]: Not your badge"
+                );
+                let data = badge.data();
+                let role = match data.contract_kind {
+                    ContractKind::Project => {
+                        Global::<Project>::from(data.contract_address).role(self.admin_badge)
+                    }
+                    ContractKind::Job => {
+                        Global::<Job>::from(data.contract_address).role(self.admin_badge)
+                    }
+                };
+                assert!(
+                    role != ContractRole::Nonmember,
+                    "[Deposit Badges]: Not a member"
+                );
+                self.keep_badge(data.contract_address, badge_bucket);
             }
         }
 
         pub fn withdraw(&mut self, resource_address: ResourceAddress) -> Bucket {
-            self.resources
-                .get_mut(&resource_address)
-                .unwrap()
-                .take_all()
+            self.treasury.withdraw(resource_address)
+        }
+
+        pub fn withdraw_amount(
+            &mut self,
+            resource_address: ResourceAddress,
+            amount: Decimal,
+        ) -> Bucket {
+            self.treasury.withdraw_amount(resource_address, amount)
+        }
+
+        pub fn withdraw_ids(
+            &mut self,
+            resource_address: ResourceAddress,
+            ids: IndexSet<NonFungibleLocalId>,
+        ) -> Bucket {
+            self.treasury.withdraw_ids(resource_address, ids)
+        }
+
+        pub fn balances(&self) -> Balances {
+            self.treasury.balances()
         }
 
         pub fn update_members(&mut self, contacts: Vec<ResourceAddress>, is_remove: bool) {
@@ -204,8 +336,172 @@ mod member {
             self.admin_badge
         }
 
+        /// Guardians are other members' badges, delay is in seconds
+        pub fn set_guardians(
+            &mut self,
+            guardians: Vec<ResourceAddress>,
+            threshold: u64,
+            delay: i64,
+        ) {
+            assert!(
+                !guardians.contains(&self.admin_badge),
+                "[Set Guardians]: Cannot guard self"
+            );
+            let guardians: HashSet<ResourceAddress> = guardians.into_iter().collect();
+            assert!(
+                threshold > 0 && threshold <= guardians.len() as u64,
+                "[Set Guardians]: Invalid threshold"
+            );
+            assert!(delay >= 0, "[Set Guardians]: Invalid delay");
+            self.guardians = guardians;
+            self.recovery_threshold = threshold;
+            self.recovery_delay = delay;
+            self.recovery = None;
+        }
+
+        /// Lets the badge holder stop a recovery during the delay
+        pub fn cancel_recovery(&mut self) {
+            self.recovery = None;
+        }
+
+        /// Guardians approve the account the badge is sent to,
+        /// the delay starts once an account's approvals reach the threshold
+        pub fn approve_recovery(
+            &mut self,
+            guardian_badge: ResourceAddress,
+            proof: NonFungibleProof,
+            account: ComponentAddress,
+        ) {
+            self.check_guardian(guardian_badge, proof);
+            if self.recovery.is_none() {
+                self.recovery = Some(RecoveryData {
+                    approvals: HashMap::new(),
+                    account: None,
+                    epoch: None,
+                });
+            }
+            let threshold = self.recovery_threshold;
+            let recovery = self.recovery.as_mut().unwrap();
+            recovery.approvals.insert(guardian_badge, account);
+
+            let approvals = |account: ComponentAddress, recovery: &RecoveryData| {
+                recovery
+                    .approvals
+                    .values()
+                    .filter(|approved| **approved == account)
+                    .count() as u64
+            };
+            // a guardian moving their approval can drop an account below the threshold
+            if let Some(approved_account) = recovery.account {
+                if approvals(approved_account, recovery) < threshold {
+                    recovery.account = None;
+                    recovery.epoch = None;
+                }
+            }
+            if recovery.account.is_none() && approvals(account, recovery) >= threshold {
+                recovery.account = Some(account);
+                recovery.epoch = Some(Self::get_curr_epoch());
+            }
+        }
+
+        /// Mints a replacement badge once approved & the delay has passed.
+        /// Its id is set in the badge metadata, contracts resolve it to the same handle
+        /// & no longer accept the lost badge. It's deposited to the approved account.
+        pub fn recover(&mut self, guardian_badge: ResourceAddress, proof: NonFungibleProof) {
+            self.check_guardian(guardian_badge, proof);
+            assert!(self.recovery.is_some(), "[Recover]: Not started");
+            let recovery = self.recovery.take().unwrap();
+            assert!(
+                recovery.account.is_some(),
+                "[Recover]: Not enough approvals"
+            );
+            assert!(
+                Self::get_curr_epoch() >= recovery.epoch.unwrap() + self.recovery_delay,
+                "[Recover]: Delay not passed"
+            );
+
+            self.recoveries = self.recoveries + 1;
+            let id_string = format!("{}_{}", self.member_handle, self.recoveries);
+            let badge_id =
+                NonFungibleLocalId::String(StringNonFungibleLocalId::new(&id_string).unwrap());
+            let badge_bucket = self.badge_manager.mint_non_fungible(
+                &badge_id,
+                MemberData {
+                    member_address: Runtime::global_address(),
+                },
+            );
+            self.badge_manager.set_metadata(BADGE_ID, id_string);
+            Runtime::global_component().set_role(
+                "admin",
+                rule!(require(NonFungibleGlobalId::new(
+                    self.admin_badge,
+                    badge_id.clone()
+                ))),
+            );
+            let mut account = Global::<Account>::from(recovery.account.unwrap());
+            account.try_deposit_or_abort(badge_bucket, None);
+        }
+
+        pub fn recovery(&self) -> (HashSet<ResourceAddress>, u64, i64, Option<RecoveryData>) {
+            (
+                self.guardians.clone(),
+                self.recovery_threshold,
+                self.recovery_delay,
+                self.recovery.clone(),
+            )
+        }
+
         // Private functions
 
+        fn check_guardian(&self, guardian_badge: ResourceAddress, proof: NonFungibleProof) {
+            let result = proof.check(guardian_badge);
+            // a guardian's lost badge is no longer valid once replaced
+            let manager = ResourceManager::from(guardian_badge);
+            let badge_id: Option<String> = manager.get_metadata(BADGE_ID).unwrap_or(None);
+            if let Some(badge_id) = badge_id {
+                assert!(
+                    result.non_fungible_local_id()
+                        == NonFungibleLocalId::String(
+                            StringNonFungibleLocalId::new(&badge_id).unwrap()
+                        ),
+                    "[Recovery]: Replaced badge"
+                );
+            }
+            assert!(
+                self.guardians.contains(&guardian_badge),
+                "[Recovery]: Not a guardian"
+            );
+        }
+
+        fn get_curr_epoch() -> Decimal {
+            let epoch = Clock::current_time(TimePrecision::Second).seconds_since_unix_epoch;
+            Decimal::from(epoch)
+        }
+
+        fn contract_proof(&self, contract_address: ComponentAddress) -> NonFungibleProof {
+            let contract_badge = self.contract_badges.get(&contract_address);
+            assert!(contract_badge.is_some(), "[Withdraw]: Not in custody");
+            let nft_id = StringNonFungibleLocalId::new(&self.member_handle).unwrap();
+            self.treasury
+                .create_proof(*contract_badge.unwrap(), NonFungibleLocalId::String(nft_id))
+        }
+
+        /// Custodied badges are recorded to prove withdrawals from their contract
+        fn keep_badge(
+            &mut self,
+            contract_address: ComponentAddress,
+            badge_bucket: NonFungibleBucket,
+        ) -> Option<Bucket> {
+            if self.is_custodial {
+                self.contract_badges
+                    .insert(contract_address, badge_bucket.resource_address());
+                self.deposit(Bucket::from(badge_bucket));
+                None
+            } else {
+                Some(Bucket::from(badge_bucket))
+            }
+        }
+
         fn nft_builder<D: MemberRegisteredType + NonFungibleData>(
             name: &str,
             description: &str,
@@ -230,7 +526,7 @@ mod member {
                       "icon_url" => Url::of(icon_url), updatable;
                       "info_url" => Url::of(INFO_URL), locked;
                       MEMBER_ADDRESS => GlobalAddress::from(member_address), locked;
-                      "member_handle" => member_handle, locked;
+                      MEMBER_HANDLE => member_handle, locked;
                     }
                 })
                 .mint_roles(mint_roles! {
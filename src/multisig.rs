@@ -0,0 +1,117 @@
+use crate::types::*;
+use scrypto::prelude::*;
+
+#[blueprint]
+#[types(Decimal, ProposalData)]
+mod multisig {
+    struct Multisig {
+        signers: HashSet<ResourceAddress>, // member badges allowed to propose & approve
+        threshold: u64,                    // approvals required to execute
+        proposals: KeyValueStore<Decimal, ProposalData>,
+        proposals_total: Decimal,
+    }
+
+    impl Multisig {
+        /// Creates a new Multisig
+        ///
+        /// Multisigs are owned by Projects & Jobs to require M-of-N approval of admin actions.
+        /// The contract checks the signer proofs & records the txs, the Multisig only tracks
+        /// proposals and their approvals.
+        ///
+        /// # Arguments
+        ///
+        /// * `signers` - Member badges allowed to propose & approve
+        /// * `threshold` - Number of approvals needed to execute a proposal
+        ///
+        /// # Returns
+        ///
+        /// * `Owned<Multisig>` - The created Multisig
+        ///
+        pub fn new(signers: Vec<ResourceAddress>, threshold: u64) -> Owned<Multisig> {
+            let signers: HashSet<ResourceAddress> = signers.into_iter().collect();
+            Self::check_threshold(&signers, threshold);
+            Self {
+                signers,
+                threshold,
+                proposals: KeyValueStore::<Decimal, ProposalData>::new_with_registered_type(),
+                proposals_total: dec!(0),
+            }
+            .instantiate()
+        }
+
+        /// Returns the id of the new proposal, the proposer's approval is included
+        pub fn propose(&mut self, signer: ResourceAddress, action: AdminAction) -> Decimal {
+            self.check_signer(&signer);
+            let id = self.proposals_total + 1;
+            self.proposals_total = id;
+            self.proposals.insert(
+                id,
+                ProposalData {
+                    action,
+                    proposer: signer,
+                    approvals: HashSet::from([signer]),
+                    epoch: Self::get_curr_epoch(),
+                    is_executed: false,
+                },
+            );
+            id
+        }
+
+        pub fn approve(&mut self, signer: ResourceAddress, id: Decimal) {
+            self.check_signer(&signer);
+            let mut proposal = self.proposals.get_mut(&id).unwrap();
+            assert!(!proposal.is_executed, "[Approve]: Already executed");
+            assert!(
+                proposal.approvals.insert(signer),
+                "[Approve]: Already approved"
+            );
+        }
+
+        /// Marks the proposal as executed & returns the action for the contract to run
+        pub fn execute(&mut self, signer: ResourceAddress, id: Decimal) -> AdminAction {
+            self.check_signer(&signer);
+            let mut proposal = self.proposals.get_mut(&id).unwrap();
+            assert!(!proposal.is_executed, "[Execute]: Already executed");
+            // approvals of rotated out signers no longer count
+            let approvals = proposal.approvals.intersection(&self.signers).count();
+            assert!(
+                approvals as u64 >= self.threshold,
+                "[Execute]: Not enough approvals"
+            );
+            proposal.is_executed = true;
+            proposal.action.clone()
+        }
+
+        /// Rotates the signers, run by the contract once a SetSigners proposal is executed
+        pub fn set_signers(&mut self, signers: Vec<ResourceAddress>, threshold: u64) {
+            let signers: HashSet<ResourceAddress> = signers.into_iter().collect();
+            Self::check_threshold(&signers, threshold);
+            self.signers = signers;
+            self.threshold = threshold;
+        }
+
+        pub fn get_proposal(&self, id: Decimal) -> Option<ProposalData> {
+            self.proposals.get(&id).map(|proposal| (*proposal).clone())
+        }
+
+        pub fn get_signers(&self) -> (HashSet<ResourceAddress>, u64) {
+            (self.signers.clone(), self.threshold)
+        }
+
+        fn check_signer(&self, signer: &ResourceAddress) {
+            assert!(self.signers.contains(signer), "[Multisig]: Not a signer");
+        }
+
+        fn check_threshold(signers: &HashSet<ResourceAddress>, threshold: u64) {
+            assert!(
+                threshold > 0 && threshold <= signers.len() as u64,
+                "[Multisig]: Invalid threshold"
+            );
+        }
+
+        fn get_curr_epoch() -> Decimal {
+            let epoch = Clock::current_time(TimePrecision::Second).seconds_since_unix_epoch;
+            Decimal::from(epoch)
+        }
+    }
+}
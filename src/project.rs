@@ -1,14 +1,40 @@
 use crate::badge_manager::badge_manager::BadgeManager;
 use crate::marketplace::marketplace::Marketplace;
 use crate::member::member::Member;
+use crate::multisig::multisig::Multisig;
 use crate::types::*;
 use scrypto::prelude::*;
 
 #[blueprint]
+#[events(
+    CreateEvent,
+    DetailsEvent,
+    DepositEvent,
+    ListEvent,
+    InviteEvent,
+    RemoveEvent,
+    LeaveEvent,
+    UpdateEvent,
+    JoinEvent,
+    RewardEvent,
+    WithdrawEvent,
+    CancellationEvent,
+    DisputeEvent,
+    ResolveEvent,
+    DelistEvent,
+    ApplyEvent,
+    RejectEvent,
+    ProposeEvent,
+    ApproveEvent,
+    ExecuteEvent,
+    NoticeEvent,
+    RefundEvent
+)]
 mod project {
     enable_method_auth! {
         roles {
-            admin => updatable_by: [];
+            // updated by the component when the admin badge is recovered
+            admin => updatable_by: [SELF];
         },
         methods {
             init => PUBLIC;
@@ -16,15 +42,42 @@ mod project {
             remove => restrict_to: [admin, SELF];
             leave => PUBLIC;
             join => PUBLIC;
+            apply => PUBLIC;
+            accept_application => restrict_to: [admin];
+            reject_application => restrict_to: [admin];
+            clear_applications => restrict_to: [admin];
+            applications => PUBLIC;
             deposit => restrict_to: [admin];
+            deposit_nfts => restrict_to: [admin];
+            signer_deposit => PUBLIC;
+            signer_deposit_nfts => PUBLIC;
             update => restrict_to: [admin];
             details => restrict_to: [admin];
             reward => restrict_to: [admin];
             withdraw => PUBLIC;
-            cancellation => restrict_to: [admin];
+            request_cancellation => restrict_to: [admin];
+            finalize_cancellation => restrict_to: [admin];
+            withdraw_refunds => restrict_to: [admin];
+            dispute => PUBLIC;
+            resolve => PUBLIC;
             list => restrict_to: [admin];
+            delist => PUBLIC;
             data => PUBLIC;
+            balances => PUBLIC;
+            txs => PUBLIC;
+            txs_by_type => PUBLIC;
+            txs_by_badge => PUBLIC;
+            txs_total => PUBLIC;
+            tx_years => PUBLIC;
+            tx_year => PUBLIC;
             role => PUBLIC;
+            update_admin => PUBLIC;
+            set_multisig => restrict_to: [admin];
+            propose => PUBLIC;
+            approve => PUBLIC;
+            execute => PUBLIC;
+            proposal => PUBLIC;
+            signers => PUBLIC;
         }
     }
 
@@ -41,25 +94,48 @@ mod project {
         admin_badge: ResourceAddress,
         admin_handle: String,
         member_badges: HashMap<ResourceAddress, String>,
+        applications: HashMap<ResourceAddress, ApplicationData>,
         removed: HashMap<ResourceAddress, String>,
         signatures: HashSet<ResourceAddress>,
-        funds: FungibleVault,
+        // unallocated funds by resource, the listing resource's vault always exists
+        funds: HashMap<ResourceAddress, FungibleVault>,
         resource_address: ResourceAddress,
 
         start_epoch: i64,
         end_epoch: i64,
-        amount: Decimal,
-        rewarded: Decimal,
-        withdrawn: Decimal,
+        amounts: HashMap<ResourceAddress, Decimal>,
+        rewarded: HashMap<ResourceAddress, Decimal>,
+        withdrawn: HashMap<ResourceAddress, Decimal>,
 
-        objectives: HashMap<Decimal, HashMap<ResourceAddress, Decimal>>,
-        completed: HashMap<Decimal, HashMap<ResourceAddress, Decimal>>,
-        reserved: HashMap<ResourceAddress, FungibleVault>,
+        // objective number -> member badge -> resource -> amount
+        objectives: HashMap<Decimal, HashMap<ResourceAddress, HashMap<ResourceAddress, Decimal>>>,
+        completed: HashMap<Decimal, HashMap<ResourceAddress, HashMap<ResourceAddress, Decimal>>>,
+        reserved: HashMap<ResourceAddress, HashMap<ResourceAddress, FungibleVault>>,
+        arbiter_badge: Option<ResourceAddress>,
+        disputes: HashMap<Decimal, HashMap<ResourceAddress, HashMap<ResourceAddress, Decimal>>>,
+        frozen: HashMap<ResourceAddress, FungibleVault>,
+        // dispute refunds resolved after cancellation, held for the admin
+        refunds: HashMap<ResourceAddress, FungibleVault>,
+        // non-fungibles held in escrow until an objective assigning them is rewarded
+        escrow: HashMap<ResourceAddress, NonFungibleVault>,
+        // objective number -> member badge -> resource -> non-fungible ids
+        nft_objectives: HashMap<
+            Decimal,
+            HashMap<ResourceAddress, HashMap<ResourceAddress, IndexSet<NonFungibleLocalId>>>,
+        >,
+        nft_completed: HashMap<
+            Decimal,
+            HashMap<ResourceAddress, HashMap<ResourceAddress, IndexSet<NonFungibleLocalId>>>,
+        >,
+        nft_reserved: HashMap<ResourceAddress, HashMap<ResourceAddress, NonFungibleVault>>,
         max_members: u8,
         is_cancelled: bool,
         cancelled_epoch: Decimal,
         created_epoch: Decimal,
         list_epoch: Decimal,
+        notice_period: i64,
+        cancel_request_epoch: Option<Decimal>,
+        multisig: Option<Owned<Multisig>>,
     }
 
     impl Project {
@@ -75,6 +151,8 @@ mod project {
             start_epoch: i64,
             end_epoch: i64,
             max_members: u8,
+            arbiter_badge: Option<ResourceAddress>,
+            notice_period: i64,
             image: String,
             category: String,
             details: HashMap<String, String>,
@@ -82,13 +160,15 @@ mod project {
             let (address_reservation, component_address) =
                 Runtime::allocate_component_address(Project::blueprint_id());
 
-            let admin_handle = Self::get_proof_id(&admin_badge, admin_proof);
+            let admin_handle = Self::get_member_handle(&admin_badge, admin_proof);
             let badge_manager = BadgeManager::new(
                 component_address,
                 ContractKind::Project,
                 contract_name.clone(),
             );
             assert!(end_epoch >= start_epoch, "[Instantiate]: Invalid Dates");
+            // a zero notice period would let a cancellation be requested & finalized in one tx
+            assert!(notice_period > 0, "[Instantiate]: Invalid notice period");
             let new_details = KeyValueStore::<String, String>::new();
             for (key, value) in details.iter() {
                 new_details.insert(key.to_owned(), value.to_owned());
@@ -107,25 +187,37 @@ mod project {
                 admin_badge,
                 admin_handle,
                 member_badges: HashMap::new(),
+                applications: HashMap::new(),
                 removed: HashMap::new(),
                 signatures: HashSet::new(),
-                funds: FungibleVault::new(resource_address),
+                funds: HashMap::from([(resource_address, FungibleVault::new(resource_address))]),
                 resource_address,
 
                 start_epoch,
                 end_epoch,
-                amount: dec!(0),
-                rewarded: dec!(0),
-                withdrawn: dec!(0),
+                amounts: HashMap::new(),
+                rewarded: HashMap::new(),
+                withdrawn: HashMap::new(),
 
                 objectives: HashMap::new(),
                 completed: HashMap::new(),
                 reserved: HashMap::new(),
+                arbiter_badge,
+                disputes: HashMap::new(),
+                frozen: HashMap::new(),
+                refunds: HashMap::new(),
+                escrow: HashMap::new(),
+                nft_objectives: HashMap::new(),
+                nft_completed: HashMap::new(),
+                nft_reserved: HashMap::new(),
                 max_members,
                 is_cancelled: false,
                 cancelled_epoch: dec!(0),
                 created_epoch: Self::get_curr_epoch(),
                 list_epoch: dec!(0),
+                notice_period,
+                cancel_request_epoch: None,
+                multisig: None,
             }
             .instantiate()
             .prepare_to_globalize(OwnerRole::None)
@@ -173,7 +265,8 @@ mod project {
                 self.admin_badge,
                 self.contract_handle.clone(),
                 self.badge_manager.badge(),
-                self.funds.amount(),
+                None,
+                dec!(0),
                 TxType::Create,
             );
 
@@ -181,55 +274,88 @@ mod project {
         }
 
         pub fn invite(&mut self, member_badge: ResourceAddress, member_handle: String) {
+            assert!(self.multisig.is_none(), "[Invite]: Requires proposal");
+            self.add_member(member_badge, member_handle);
+        }
+
+        pub fn remove(&mut self, member_badge: ResourceAddress) {
+            assert!(self.multisig.is_none(), "[Remove]: Requires proposal");
+            self.remove_member(member_badge);
+        }
+
+        pub fn leave(&mut self, member_badge: ResourceAddress, proof: NonFungibleProof) {
+            self.check_proof(&member_badge, proof);
+            self.remove_member(member_badge);
+        }
+
+        pub fn apply(
+            &mut self,
+            member_badge: ResourceAddress,
+            proof: NonFungibleProof,
+            details: HashMap<String, String>,
+        ) {
+            let member_handle = Self::get_member_handle(&member_badge, proof);
+            assert!(self.is_joinable(), "[Apply]: Not joinable");
             assert!(
-                self.member_badges.len() <= MAX_MEMBERS,
-                "[Invite]: Too many members"
+                !self.member_badges.contains_key(&member_badge),
+                "[Apply]: Already Added"
             );
             assert!(
-                !self.member_badges.contains_key(&member_badge),
-                "[Add Member]: Already Added"
+                !self.applications.contains_key(&member_badge),
+                "[Apply]: Already Applied"
+            );
+            assert!(
+                self.applications.len() < MAX_APPLICATIONS,
+                "[Apply]: Too many applications"
+            );
+            self.applications.insert(
+                member_badge,
+                ApplicationData {
+                    handle: member_handle.clone(),
+                    epoch: Self::get_curr_epoch(),
+                    details,
+                },
             );
-            let is_valid = ResourceManager::from(member_badge)
-                .non_fungible_exists(&BadgeManager::nft_id(member_handle.clone()));
-            assert!(is_valid, "[Invite]: Not valid");
-            self.member_badges
-                .insert(member_badge, member_handle.clone());
 
             // CREATE TXS
             self.create_tx(
-                self.admin_handle.clone(),
-                self.admin_badge,
                 member_handle,
                 member_badge,
+                self.contract_handle.clone(),
+                self.badge_manager.badge(),
+                None,
                 dec!(0),
-                TxType::Invite,
+                TxType::Apply,
             );
         }
 
-        pub fn remove(&mut self, member_badge: ResourceAddress) {
-            for (_, members) in self.objectives.iter_mut() {
-                members.remove(&member_badge);
-            }
+        pub fn accept_application(&mut self, member_badge: ResourceAddress) {
+            assert!(
+                self.multisig.is_none(),
+                "[Accept Application]: Requires proposal"
+            );
+            self.accept_member(member_badge);
+        }
 
-            let handle = self.member_badges.remove(&member_badge).unwrap();
-            if self.signatures.contains(&member_badge) {
-                self.removed.insert(member_badge, handle.clone());
-            }
+        pub fn reject_application(&mut self, member_badge: ResourceAddress) {
+            assert!(
+                self.multisig.is_none(),
+                "[Reject Application]: Requires proposal"
+            );
+            self.reject_member(member_badge);
+        }
 
-            // CREATE TXS
-            self.create_tx(
-                self.admin_handle.clone(),
-                self.admin_badge,
-                handle,
-                member_badge,
-                dec!(0),
-                TxType::Remove,
+        /// Rejects every pending application
+        pub fn clear_applications(&mut self) {
+            assert!(
+                self.multisig.is_none(),
+                "[Clear Applications]: Requires proposal"
             );
+            self.reject_members();
         }
 
-        pub fn leave(&mut self, member_badge: ResourceAddress, proof: NonFungibleProof) {
-            self.check_proof(&member_badge, proof);
-            self.remove(member_badge);
+        pub fn applications(&self) -> HashMap<ResourceAddress, ApplicationData> {
+            self.applications.clone()
         }
 
         pub fn join(
@@ -260,6 +386,7 @@ mod project {
                 member_badge,
                 self.contract_handle.clone(),
                 self.badge_manager.badge(),
+                None,
                 dec!(0),
                 TxType::Join,
             );
@@ -268,55 +395,49 @@ mod project {
         }
 
         pub fn deposit(&mut self, funds: FungibleBucket) {
-            assert!(!self.is_cancelled, "[Deposit]: Is Cancelled");
-            Self::check_funds(&funds);
-
-            // CREATE TXS
-            self.create_tx(
-                self.admin_handle.clone(),
-                self.admin_badge,
-                self.contract_handle.clone(),
-                self.badge_manager.badge(),
-                funds.amount(),
-                TxType::Deposit,
-            );
+            assert!(self.multisig.is_none(), "[Deposit]: Requires signer");
+            self.deposit_funds(self.admin_handle.clone(), self.admin_badge, funds);
+        }
 
-            self.amount = self.amount + funds.amount();
-            self.funds.put(funds);
+        pub fn deposit_nfts(&mut self, nfts: NonFungibleBucket) {
+            assert!(self.multisig.is_none(), "[Deposit]: Requires signer");
+            self.deposit_escrow(self.admin_handle.clone(), self.admin_badge, nfts);
         }
 
-        pub fn update(&mut self, objectives: HashMap<Decimal, HashMap<ResourceAddress, Decimal>>) {
-            let total_objs = objectives.len() + self.completed.len();
-            assert!(total_objs <= MAX_OBJS, "[Update]: Too many objectives");
+        /// Deposits can't be proposed, with a multisig any signer deposits instead of the admin
+        pub fn signer_deposit(
+            &mut self,
+            signer_badge: ResourceAddress,
+            proof: NonFungibleProof,
+            funds: FungibleBucket,
+        ) {
+            let signer_handle = self.check_signer(&signer_badge, proof);
+            self.deposit_funds(signer_handle, signer_badge, funds);
+        }
 
-            let mut total = dec!("0");
-            for (obj_num, members) in objectives.iter() {
-                assert!(!members.is_empty(), "[Update]: Empty Members");
-                for (member, amount) in members.iter() {
-                    if self.completed.contains_key(obj_num) {
-                        let com_dis = self.completed.get(obj_num).unwrap();
-                        assert!(!com_dis.contains_key(member), "[Update]: Completed");
-                    }
-                    assert!(
-                        self.member_badges.contains_key(member),
-                        "[Update]: No Member"
-                    );
-                    assert!(amount > &dec!("0"), "[Update]: No Amount");
-                    total = total + amount.clone();
-                }
-            }
-            assert!(total == self.funds.amount(), "[Update]: Invalid Sum");
-            self.objectives = objectives;
+        pub fn signer_deposit_nfts(
+            &mut self,
+            signer_badge: ResourceAddress,
+            proof: NonFungibleProof,
+            nfts: NonFungibleBucket,
+        ) {
+            let signer_handle = self.check_signer(&signer_badge, proof);
+            self.deposit_escrow(signer_handle, signer_badge, nfts);
+        }
 
-            // CREATE TXS
-            self.create_tx(
-                self.admin_handle.clone(),
-                self.admin_badge,
-                self.contract_handle.clone(),
-                self.badge_manager.badge(),
-                dec!(0),
-                TxType::Update,
-            );
+        pub fn update(
+            &mut self,
+            objectives: HashMap<
+                Decimal,
+                HashMap<ResourceAddress, HashMap<ResourceAddress, Decimal>>,
+            >,
+            nft_objectives: HashMap<
+                Decimal,
+                HashMap<ResourceAddress, HashMap<ResourceAddress, IndexSet<NonFungibleLocalId>>>,
+            >,
+        ) {
+            assert!(self.multisig.is_none(), "[Update]: Requires proposal");
+            self.set_objectives(objectives, nft_objectives);
         }
 
         pub fn details(
@@ -327,96 +448,273 @@ mod project {
             image: String,
             details: HashMap<String, String>,
         ) {
-            assert!(end_epoch >= start_epoch, "[Instantiate]: Invalid Dates");
-            self.start_epoch = start_epoch;
-            self.end_epoch = end_epoch;
-            self.image = Url::of(image);
-            for (key, value) in details.iter() {
-                self.details.insert(key.to_owned(), value.to_owned());
+            assert!(self.multisig.is_none(), "[Details]: Requires proposal");
+            self.set_details(start_epoch, end_epoch, max_members, image, details);
+        }
+
+        pub fn reward(&mut self, obj_number: Decimal) {
+            assert!(self.multisig.is_none(), "[Reward]: Requires proposal");
+            self.reward_objective(obj_number);
+        }
+
+        pub fn withdraw(
+            &mut self,
+            member_badge: ResourceAddress,
+            proof: NonFungibleProof,
+        ) -> Vec<Bucket> {
+            self.check_list();
+            let member_handle = self.check_proof(&member_badge, proof);
+            let mut buckets: Vec<Bucket> = Vec::new();
+            if let Some(vaults) = self.reserved.get_mut(&member_badge) {
+                for (_, vault) in vaults.iter_mut() {
+                    if !vault.is_empty() {
+                        buckets.push(Bucket::from(vault.take_all()));
+                    }
+                }
             }
-            self.max_members = max_members;
+            if let Some(vaults) = self.nft_reserved.get_mut(&member_badge) {
+                for (_, vault) in vaults.iter_mut() {
+                    if !vault.is_empty() {
+                        buckets.push(Bucket::from(vault.take_all()));
+                    }
+                }
+            }
+            assert!(!buckets.is_empty(), "[Withdraw]: Is empty");
 
-            // CREATE TXS
-            self.create_tx(
-                self.admin_handle.clone(),
-                self.admin_badge,
-                self.contract_handle.clone(),
-                self.badge_manager.badge(),
-                dec!(0),
-                TxType::Details,
-            );
+            for bucket in buckets.iter() {
+                let resource_address = bucket.resource_address();
+                // CREATE TXS
+                if resource_address.is_fungible() {
+                    Self::add_amount(&mut self.withdrawn, resource_address, bucket.amount());
+                    self.create_tx(
+                        self.contract_handle.clone(),
+                        self.badge_manager.badge(),
+                        member_handle.clone(),
+                        member_badge,
+                        Some(resource_address),
+                        bucket.amount(),
+                        TxType::Withdraw,
+                    );
+                } else {
+                    self.create_nft_tx(
+                        self.contract_handle.clone(),
+                        self.badge_manager.badge(),
+                        member_handle.clone(),
+                        member_badge,
+                        resource_address,
+                        bucket.as_non_fungible().non_fungible_local_ids(),
+                        TxType::Withdraw,
+                    );
+                }
+            }
+
+            buckets
         }
 
-        pub fn reward(&mut self, obj_number: Decimal) {
-            let members = self.objectives.remove(&obj_number).unwrap();
-            for (member, amount) in members.iter() {
-                assert!(self.signatures.contains(member), "[Reward]: No signature");
-                let pay_bucket = self.funds.take_advanced(
+        /// Starts the notice period, pending objectives can still be rewarded until it's finalized
+        pub fn request_cancellation(&mut self) {
+            assert!(self.multisig.is_none(), "[Cancellation]: Requires proposal");
+            self.request_cancel();
+        }
+
+        pub fn finalize_cancellation(&mut self) -> Vec<Bucket> {
+            assert!(self.multisig.is_none(), "[Cancellation]: Requires proposal");
+            self.finalize_cancel()
+        }
+
+        /// Disputes a member's fungible amounts, assigned non-fungibles stay in escrow
+        pub fn dispute(
+            &mut self,
+            obj_number: Decimal,
+            member_badge: ResourceAddress,
+            proof: NonFungibleProof,
+        ) {
+            assert!(!self.is_cancelled, "[Dispute]: Is Cancelled");
+            assert!(self.arbiter_badge.is_some(), "[Dispute]: No arbiter");
+            // Objectives can only be disputed once overdue or while a cancellation is pending
+            assert!(
+                Self::get_curr_epoch() >= Decimal::from(self.end_epoch)
+                    || self.cancel_request_epoch.is_some(),
+                "[Dispute]: Not overdue"
+            );
+            let member_handle = self.check_proof(&member_badge, proof);
+            assert!(
+                self.signatures.contains(&member_badge),
+                "[Dispute]: No signature"
+            );
+
+            // The arbiter only splits fungible amounts, nft-only objectives can't be disputed
+            let has_amounts = self
+                .objectives
+                .get(&obj_number)
+                .map_or(false, |members| members.contains_key(&member_badge));
+            assert!(has_amounts, "[Dispute]: No fungible amounts");
+
+            // Remove the member from the objective so it can't be rewarded or updated
+            let members = self.objectives.get_mut(&obj_number).unwrap();
+            let amounts = members.remove(&member_badge).unwrap();
+            if members.is_empty() {
+                self.objectives.remove(&obj_number);
+            }
+
+            // Freeze the amounts so cancellation can't sweep them
+            let mut frozen_amounts = HashMap::new();
+            for (resource_address, amount) in amounts.iter() {
+                let frozen_bucket = self.funds.get_mut(resource_address).unwrap().take_advanced(
                     amount.clone(),
                     WithdrawStrategy::Rounded(RoundingMode::ToZero),
                 );
-                assert!(!pay_bucket.is_empty(), "[Reward]: No funds");
-
-                let new_amount = pay_bucket.amount();
-                self.rewarded = self.rewarded + new_amount;
-                // Deposit into Reserved Vaults
-                if !self.reserved.contains_key(&member) {
-                    self.reserved
-                        .insert(member.clone(), FungibleVault::with_bucket(pay_bucket));
+                let frozen_amount = frozen_bucket.amount();
+                frozen_amounts.insert(resource_address.clone(), frozen_amount);
+                if !self.frozen.contains_key(resource_address) {
+                    self.frozen.insert(
+                        resource_address.clone(),
+                        FungibleVault::with_bucket(frozen_bucket),
+                    );
                 } else {
-                    let vault = self.reserved.get_mut(&member).unwrap();
-                    vault.put(pay_bucket);
+                    self.frozen
+                        .get_mut(resource_address)
+                        .unwrap()
+                        .put(frozen_bucket);
                 }
+
                 // CREATE TXS
-                let handle = self.member_badges.get(&member).unwrap().to_owned();
                 self.create_tx(
+                    member_handle.clone(),
+                    member_badge,
                     self.contract_handle.clone(),
                     self.badge_manager.badge(),
-                    handle,
-                    member.clone(),
-                    new_amount,
-                    TxType::Reward,
+                    Some(*resource_address),
+                    frozen_amount,
+                    TxType::Dispute,
                 );
             }
-            assert!(
-                !self.completed.contains_key(&obj_number),
-                "[Reward]: Already completed"
-            );
-            self.completed.insert(obj_number, members);
+            if !self.disputes.contains_key(&obj_number) {
+                self.disputes.insert(obj_number, HashMap::new());
+            }
+            self.disputes
+                .get_mut(&obj_number)
+                .unwrap()
+                .insert(member_badge, frozen_amounts);
         }
 
-        pub fn withdraw(
+        pub fn resolve(
             &mut self,
+            obj_number: Decimal,
             member_badge: ResourceAddress,
+            member_amounts: HashMap<ResourceAddress, Decimal>,
             proof: NonFungibleProof,
-        ) -> FungibleBucket {
-            self.check_list();
-            let member_handle = self.check_proof(&member_badge, proof);
-            let vault = self.reserved.get_mut(&member_badge).unwrap();
-            let bucket = vault.take_all();
-            assert!(!bucket.is_empty(), "[Withdraw]: Is empty");
-            let withdrawn = bucket.amount();
-            self.withdrawn = self.withdrawn + withdrawn;
+        ) {
+            let arbiter_badge = self.arbiter_badge.expect("[Resolve]: No arbiter");
+            let arbiter_handle = Self::get_member_handle(&arbiter_badge, proof);
 
-            // CREATE TXS
-            self.create_tx(
-                self.contract_handle.clone(),
-                self.badge_manager.badge(),
-                member_handle,
-                member_badge,
-                withdrawn,
-                TxType::Withdraw,
+            let members = self
+                .disputes
+                .get_mut(&obj_number)
+                .expect("[Resolve]: No dispute");
+            let amounts = members
+                .remove(&member_badge)
+                .expect("[Resolve]: Member not disputed");
+            if members.is_empty() {
+                self.disputes.remove(&obj_number);
+            }
+            assert!(
+                member_amounts
+                    .keys()
+                    .all(|resource| amounts.contains_key(resource)),
+                "[Resolve]: Invalid resource"
+            );
+
+            let handle = self
+                .member_badges
+                .get(&member_badge)
+                .expect("[Resolve]: Not a member")
+                .to_owned();
+            for (resource_address, amount) in amounts.iter() {
+                let member_amount = member_amounts
+                    .get(resource_address)
+                    .cloned()
+                    .unwrap_or(dec!(0));
+                assert!(
+                    member_amount >= dec!(0) && member_amount <= amount.clone(),
+                    "[Resolve]: Invalid amount"
+                );
+
+                // Member's share is rewarded into their reserved vault
+                let mut new_amount = dec!(0);
+                if member_amount > dec!(0) {
+                    let pay_bucket = self
+                        .frozen
+                        .get_mut(resource_address)
+                        .unwrap()
+                        .take_advanced(
+                            member_amount,
+                            WithdrawStrategy::Rounded(RoundingMode::ToZero),
+                        );
+                    new_amount = pay_bucket.amount();
+                    self.set_reserved(&member_badge, pay_bucket);
+                }
+                self.create_tx(
+                    arbiter_handle.clone(),
+                    arbiter_badge,
+                    handle.clone(),
+                    member_badge,
+                    Some(*resource_address),
+                    new_amount,
+                    TxType::Resolve,
+                );
+
+                // Admin's share is refunded to the unallocated funds,
+                // or held for the admin if the funds were already swept by cancellation
+                let refund_amount = amount.clone() - new_amount;
+                if refund_amount > dec!(0) {
+                    let refund_bucket = self
+                        .frozen
+                        .get_mut(resource_address)
+                        .unwrap()
+                        .take(refund_amount);
+                    if self.is_cancelled {
+                        self.set_refund(refund_bucket);
+                    } else {
+                        self.funds
+                            .get_mut(resource_address)
+                            .unwrap()
+                            .put(refund_bucket);
+                    }
+                    self.create_tx(
+                        arbiter_handle.clone(),
+                        arbiter_badge,
+                        self.admin_handle.clone(),
+                        self.admin_badge,
+                        Some(*resource_address),
+                        refund_amount,
+                        TxType::Refund,
+                    );
+                }
+            }
+        }
+
+        pub fn withdraw_refunds(&mut self) -> Vec<Bucket> {
+            assert!(
+                self.multisig.is_none(),
+                "[Withdraw Refunds]: Requires proposal"
             );
+            self.take_refunds()
+        }
 
-            bucket
+        pub fn list(&mut self, marketplace_address: ComponentAddress) {
+            assert!(self.multisig.is_none(), "[List]: Requires proposal");
+            self.add_marketplace(marketplace_address);
         }
 
-        pub fn cancellation(&mut self) -> FungibleBucket {
-            self.check_list();
-            self.objectives = HashMap::new();
-            self.is_cancelled = true;
-            self.cancelled_epoch = Self::get_curr_epoch();
-            let total = self.funds.take_all();
+        /// Called by a listed marketplace after removing the contract from its category
+        pub fn delist(&mut self, marketplace_address: ComponentAddress) {
+            assert!(
+                self.marketplaces.contains(&marketplace_address),
+                "[Delist]: Not listed"
+            );
+            Runtime::assert_access_rule(rule!(require(global_caller(marketplace_address))));
+            self.marketplaces.remove(&marketplace_address);
 
             // CREATE TXS
             self.create_tx(
@@ -424,58 +722,198 @@ mod project {
                 self.badge_manager.badge(),
                 self.admin_handle.clone(),
                 self.admin_badge,
-                total.amount(),
-                TxType::Cancellation,
+                None,
+                dec!(0),
+                TxType::Delist,
             );
+        }
 
-            total
+        /// Requires the replacement id once the admin badge is recovered, the lost badge loses the admin role
+        pub fn update_admin(&mut self) {
+            let badge_id: Option<String> = ResourceManager::from(self.admin_badge)
+                .get_metadata(BADGE_ID)
+                .unwrap_or(None);
+            let badge_id = badge_id.expect("[Update Admin]: Not recovered");
+            let badge_id =
+                NonFungibleLocalId::String(StringNonFungibleLocalId::new(&badge_id).unwrap());
+            Runtime::global_component().set_role(
+                "admin",
+                rule!(require(NonFungibleGlobalId::new(
+                    self.admin_badge,
+                    badge_id
+                ))),
+            );
         }
 
-        pub fn list(&mut self, marketplace_address: ComponentAddress) {
-            let marketplace = Global::<Marketplace>::from(marketplace_address);
-            marketplace.check_contract(
-                self.category.clone(),
-                ContractKind::Project,
-                self.amount,
-                self.funds.resource_address(),
+        /// Once set, the ProjectAction methods must be proposed & approved by the signers
+        pub fn set_multisig(&mut self, signers: Vec<ResourceAddress>, threshold: u64) {
+            assert!(
+                self.multisig.is_none(),
+                "[Multisig]: Already set, propose SetSigners to rotate"
             );
-            assert!(self.marketplaces.is_empty(), "[List]: Already added");
-            self.marketplaces.insert(marketplace_address);
+            self.get_admin_member();
+            self.multisig = Some(Multisig::new(signers, threshold));
+        }
 
-            self.list_epoch = Self::get_curr_epoch();
+        pub fn propose(
+            &mut self,
+            signer_badge: ResourceAddress,
+            proof: NonFungibleProof,
+            action: ProjectAction,
+        ) -> Decimal {
+            let signer_handle = Self::get_member_handle(&signer_badge, proof);
+            let proposal_id = self
+                .get_multisig()
+                .propose(signer_badge, AdminAction::Project(action));
 
             // CREATE TXS
-            self.create_tx(
-                self.admin_handle.clone(),
-                self.admin_badge,
-                self.contract_handle.clone(),
-                self.badge_manager.badge(),
-                dec!(0),
-                TxType::List,
-            );
+            self.create_proposal_tx(signer_handle, signer_badge, proposal_id, TxType::Propose);
+
+            proposal_id
         }
 
-        pub fn data(
-            &self,
-        ) -> (
-            HashSet<ComponentAddress>,
-            String,
-            ResourceAddress,
-            Decimal,
-            ResourceAddress,
-            bool,
-            ComponentAddress,
+        pub fn approve(
+            &mut self,
+            signer_badge: ResourceAddress,
+            proof: NonFungibleProof,
+            proposal_id: Decimal,
         ) {
-            (
-                self.marketplaces.clone(),
-                self.category.clone(),
-                self.admin_badge,
-                self.amount - self.rewarded,
-                self.funds.resource_address(),
-                self.member_badges.len() < usize::from(self.max_members) && !self.is_cancelled,
-                Runtime::global_address(),
-            )
-        }
+            let signer_handle = Self::get_member_handle(&signer_badge, proof);
+            self.get_multisig().approve(signer_badge, proposal_id);
+
+            // CREATE TXS
+            self.create_proposal_tx(signer_handle, signer_badge, proposal_id, TxType::Approve);
+        }
+
+        /// Any signer can execute, the buckets of a FinalizeCancellation or WithdrawRefunds
+        /// go to the admin's Member
+        pub fn execute(
+            &mut self,
+            signer_badge: ResourceAddress,
+            proof: NonFungibleProof,
+            proposal_id: Decimal,
+        ) {
+            let signer_handle = Self::get_member_handle(&signer_badge, proof);
+            let action = match self.get_multisig().execute(signer_badge, proposal_id) {
+                AdminAction::Project(action) => action,
+                AdminAction::Job(_) => Runtime::panic(String::from("[Execute]: Invalid action")),
+            };
+
+            // CREATE TXS
+            self.create_proposal_tx(signer_handle, signer_badge, proposal_id, TxType::Execute);
+
+            match action {
+                ProjectAction::Invite(member_badge, member_handle) => {
+                    self.add_member(member_badge, member_handle)
+                }
+                ProjectAction::AcceptApplication(member_badge) => self.accept_member(member_badge),
+                ProjectAction::RejectApplication(member_badge) => self.reject_member(member_badge),
+                ProjectAction::ClearApplications => self.reject_members(),
+                ProjectAction::Update(objectives, nft_objectives) => {
+                    self.set_objectives(objectives, nft_objectives)
+                }
+                ProjectAction::Reward(obj_number) => self.reward_objective(obj_number),
+                ProjectAction::Details(start_epoch, end_epoch, max_members, image, details) => {
+                    self.set_details(start_epoch, end_epoch, max_members, image, details)
+                }
+                ProjectAction::Remove(member_badge) => self.remove_member(member_badge),
+                ProjectAction::List(marketplace_address) => {
+                    self.add_marketplace(marketplace_address)
+                }
+                ProjectAction::Cancellation => self.request_cancel(),
+                ProjectAction::SetSigners(signers, threshold) => {
+                    self.get_multisig().set_signers(signers, threshold)
+                }
+                ProjectAction::WithdrawRefunds => {
+                    let buckets = self.take_refunds();
+                    self.get_admin_member().receive_funds(buckets);
+                }
+                ProjectAction::FinalizeCancellation => {
+                    let buckets = self.finalize_cancel();
+                    self.get_admin_member().receive_funds(buckets);
+                }
+            }
+        }
+
+        pub fn proposal(&self, proposal_id: Decimal) -> Option<ProposalData> {
+            self.get_multisig().get_proposal(proposal_id)
+        }
+
+        pub fn signers(&self) -> Option<(HashSet<ResourceAddress>, u64)> {
+            self.multisig
+                .as_ref()
+                .map(|multisig| multisig.get_signers())
+        }
+
+        pub fn data(
+            &self,
+        ) -> (
+            HashSet<ComponentAddress>,
+            String,
+            ResourceAddress,
+            Decimal,
+            ResourceAddress,
+            bool,
+            bool,
+            ComponentAddress,
+            i64,
+            Option<Decimal>,
+        ) {
+            (
+                self.marketplaces.clone(),
+                self.category.clone(),
+                self.admin_badge,
+                Self::get_amount(&self.amounts, &self.resource_address)
+                    - Self::get_amount(&self.rewarded, &self.resource_address),
+                self.resource_address,
+                self.is_joinable(),
+                self.is_cancelled,
+                Runtime::global_address(),
+                self.notice_period,
+                self.cancel_request_epoch,
+            )
+        }
+
+        /// Unallocated funds & escrowed non-fungibles by resource
+        pub fn balances(&self) -> Balances {
+            let mut fungibles = HashMap::new();
+            for (resource_address, vault) in self.funds.iter() {
+                fungibles.insert(resource_address.clone(), vault.amount());
+            }
+            let mut non_fungibles = HashMap::new();
+            for (resource_address, vault) in self.escrow.iter() {
+                let ids = vault.non_fungible_local_ids(u32::MAX);
+                non_fungibles.insert(resource_address.clone(), ids);
+            }
+            Balances {
+                fungibles,
+                non_fungibles,
+            }
+        }
+
+        pub fn txs(&self, start: u64, end: u64) -> Vec<TxData> {
+            self.badge_manager.get_txs(start, end, None, None)
+        }
+
+        pub fn txs_by_type(&self, tx_type: TxType, start: u64, end: u64) -> Vec<TxData> {
+            self.badge_manager.get_txs(start, end, Some(tx_type), None)
+        }
+
+        pub fn txs_by_badge(&self, badge: ResourceAddress, start: u64, end: u64) -> Vec<TxData> {
+            self.badge_manager.get_txs(start, end, None, Some(badge))
+        }
+
+        pub fn txs_total(&self) -> Decimal {
+            self.badge_manager.get_total()
+        }
+
+        pub fn tx_years(&self) -> Vec<Decimal> {
+            self.badge_manager.get_years()
+        }
+
+        pub fn tx_year(&self, year: Decimal) -> Option<YearData> {
+            self.badge_manager.get_year_data(year)
+        }
 
         pub fn role(&self, member_badge: ResourceAddress) -> ContractRole {
             let is_admin = self.admin_badge == member_badge;
@@ -491,6 +929,608 @@ mod project {
 
         // Private Funcs
 
+        fn add_member(&mut self, member_badge: ResourceAddress, member_handle: String) {
+            assert!(
+                self.member_badges.len() <= MAX_MEMBERS,
+                "[Invite]: Too many members"
+            );
+            assert!(
+                !self.member_badges.contains_key(&member_badge),
+                "[Add Member]: Already Added"
+            );
+            let is_valid = ResourceManager::from(member_badge)
+                .non_fungible_exists(&BadgeManager::nft_id(member_handle.clone()));
+            assert!(is_valid, "[Invite]: Not valid");
+            self.member_badges
+                .insert(member_badge, member_handle.clone());
+            self.applications.remove(&member_badge);
+
+            // CREATE TXS
+            self.create_tx(
+                self.admin_handle.clone(),
+                self.admin_badge,
+                member_handle,
+                member_badge,
+                None,
+                dec!(0),
+                TxType::Invite,
+            );
+            self.update_listings();
+        }
+
+        fn accept_member(&mut self, member_badge: ResourceAddress) {
+            assert!(self.is_joinable(), "[Accept Application]: Not joinable");
+            let application = self.applications.remove(&member_badge).unwrap();
+            self.add_member(member_badge, application.handle);
+        }
+
+        fn reject_member(&mut self, member_badge: ResourceAddress) {
+            let application = self.applications.remove(&member_badge).unwrap();
+
+            // CREATE TXS
+            self.create_tx(
+                self.admin_handle.clone(),
+                self.admin_badge,
+                application.handle,
+                member_badge,
+                None,
+                dec!(0),
+                TxType::Reject,
+            );
+        }
+
+        fn reject_members(&mut self) {
+            let member_badges: Vec<ResourceAddress> = self.applications.keys().cloned().collect();
+            for member_badge in member_badges {
+                self.reject_member(member_badge);
+            }
+        }
+
+        fn deposit_funds(
+            &mut self,
+            sender_handle: String,
+            sender_badge: ResourceAddress,
+            funds: FungibleBucket,
+        ) {
+            assert!(!self.is_cancelled, "[Deposit]: Is Cancelled");
+            Self::check_funds(&funds);
+            let resource_address = funds.resource_address();
+
+            // CREATE TXS
+            self.create_tx(
+                sender_handle,
+                sender_badge,
+                self.contract_handle.clone(),
+                self.badge_manager.badge(),
+                Some(funds.resource_address()),
+                funds.amount(),
+                TxType::Deposit,
+            );
+
+            Self::add_amount(&mut self.amounts, resource_address, funds.amount());
+            if self.funds.contains_key(&resource_address) {
+                self.funds.get_mut(&resource_address).unwrap().put(funds);
+            } else {
+                assert!(
+                    self.funds.len() < MAX_RESOURCES,
+                    "[Deposit]: Too many resources"
+                );
+                self.funds
+                    .insert(resource_address, FungibleVault::with_bucket(funds));
+            }
+        }
+
+        fn deposit_escrow(
+            &mut self,
+            sender_handle: String,
+            sender_badge: ResourceAddress,
+            nfts: NonFungibleBucket,
+        ) {
+            assert!(!self.is_cancelled, "[Deposit]: Is Cancelled");
+            assert!(!nfts.is_empty(), "[Deposit]: Missing NFTs");
+            let resource_address = nfts.resource_address();
+
+            // CREATE TXS
+            self.create_nft_tx(
+                sender_handle,
+                sender_badge,
+                self.contract_handle.clone(),
+                self.badge_manager.badge(),
+                resource_address,
+                nfts.non_fungible_local_ids(),
+                TxType::Deposit,
+            );
+
+            if self.escrow.contains_key(&resource_address) {
+                self.escrow.get_mut(&resource_address).unwrap().put(nfts);
+            } else {
+                assert!(
+                    self.escrow.len() < MAX_RESOURCES,
+                    "[Deposit]: Too many resources"
+                );
+                self.escrow
+                    .insert(resource_address, NonFungibleVault::with_bucket(nfts));
+            }
+        }
+
+        fn add_marketplace(&mut self, marketplace_address: ComponentAddress) {
+            let marketplace = Global::<Marketplace>::from(marketplace_address);
+            marketplace.check_contract(
+                self.category.clone(),
+                ContractKind::Project,
+                Self::get_amount(&self.amounts, &self.resource_address),
+                self.resource_address,
+            );
+            assert!(self.marketplaces.is_empty(), "[List]: Already added");
+            self.marketplaces.insert(marketplace_address);
+            if self.arbiter_badge.is_none() {
+                self.arbiter_badge = marketplace.arbiter();
+            }
+
+            self.list_epoch = Self::get_curr_epoch();
+
+            // CREATE TXS
+            self.create_tx(
+                self.admin_handle.clone(),
+                self.admin_badge,
+                self.contract_handle.clone(),
+                self.badge_manager.badge(),
+                None,
+                dec!(0),
+                TxType::List,
+            );
+        }
+
+        fn check_signer(&self, signer_badge: &ResourceAddress, proof: NonFungibleProof) -> String {
+            let signer_handle = Self::get_member_handle(signer_badge, proof);
+            let (signers, _) = self.get_multisig().get_signers();
+            assert!(signers.contains(signer_badge), "[Multisig]: Not a signer");
+            signer_handle
+        }
+
+        /// Funds of executed proposals go to the admin's Member instead of the signer
+        fn get_admin_member(&self) -> Global<Member> {
+            let member_address: Option<GlobalAddress> = ResourceManager::from(self.admin_badge)
+                .get_metadata(MEMBER_ADDRESS)
+                .unwrap_or(None);
+            let member_address = member_address.expect("[Multisig]: Admin has no Member");
+            Global::<Member>::from(ComponentAddress::try_from(member_address).unwrap())
+        }
+
+        fn set_objectives(
+            &mut self,
+            objectives: HashMap<
+                Decimal,
+                HashMap<ResourceAddress, HashMap<ResourceAddress, Decimal>>,
+            >,
+            nft_objectives: HashMap<
+                Decimal,
+                HashMap<ResourceAddress, HashMap<ResourceAddress, IndexSet<NonFungibleLocalId>>>,
+            >,
+        ) {
+            assert!(
+                self.cancel_request_epoch.is_none(),
+                "[Update]: Cancellation requested"
+            );
+            let mut obj_nums: HashSet<Decimal> = objectives.keys().cloned().collect();
+            obj_nums.extend(nft_objectives.keys().cloned());
+            let total_objs = obj_nums.len() + self.completed.len();
+            assert!(total_objs <= MAX_OBJS, "[Update]: Too many objectives");
+
+            let mut totals: HashMap<ResourceAddress, Decimal> = HashMap::new();
+            for (obj_num, members) in objectives.iter() {
+                assert!(!members.is_empty(), "[Update]: Empty Members");
+                for (member, amounts) in members.iter() {
+                    if self.completed.contains_key(obj_num) {
+                        let com_dis = self.completed.get(obj_num).unwrap();
+                        assert!(!com_dis.contains_key(member), "[Update]: Completed");
+                    }
+                    if self.disputes.contains_key(obj_num) {
+                        let disputed = self.disputes.get(obj_num).unwrap();
+                        assert!(!disputed.contains_key(member), "[Update]: Disputed");
+                    }
+                    assert!(
+                        self.member_badges.contains_key(member),
+                        "[Update]: No Member"
+                    );
+                    assert!(!amounts.is_empty(), "[Update]: No Amount");
+                    for (resource_address, amount) in amounts.iter() {
+                        assert!(
+                            self.funds.contains_key(resource_address),
+                            "[Update]: Invalid Resource"
+                        );
+                        assert!(amount > &dec!("0"), "[Update]: No Amount");
+                        Self::add_amount(&mut totals, resource_address.clone(), amount.clone());
+                    }
+                }
+            }
+            // Every resource's funds must be fully allocated
+            for (resource_address, vault) in self.funds.iter() {
+                assert!(
+                    Self::get_amount(&totals, resource_address) == vault.amount(),
+                    "[Update]: Invalid Sum"
+                );
+            }
+
+            let mut assigned: HashMap<ResourceAddress, IndexSet<NonFungibleLocalId>> =
+                HashMap::new();
+            for (obj_num, members) in nft_objectives.iter() {
+                assert!(!members.is_empty(), "[Update]: Empty Members");
+                for (member, nfts) in members.iter() {
+                    if self.nft_completed.contains_key(obj_num) {
+                        let com_dis = self.nft_completed.get(obj_num).unwrap();
+                        assert!(!com_dis.contains_key(member), "[Update]: Completed");
+                    }
+                    assert!(
+                        self.member_badges.contains_key(member),
+                        "[Update]: No Member"
+                    );
+                    assert!(!nfts.is_empty(), "[Update]: No NFTs");
+                    for (resource_address, ids) in nfts.iter() {
+                        assert!(
+                            self.escrow.contains_key(resource_address),
+                            "[Update]: Invalid Resource"
+                        );
+                        assert!(!ids.is_empty(), "[Update]: No NFTs");
+                        let vault = self.escrow.get(resource_address).unwrap();
+                        if !assigned.contains_key(resource_address) {
+                            assigned.insert(resource_address.clone(), IndexSet::new());
+                        }
+                        let assigned_ids = assigned.get_mut(resource_address).unwrap();
+                        for id in ids.iter() {
+                            assert!(vault.contains_non_fungible(id), "[Update]: Not in escrow");
+                            assert!(
+                                assigned_ids.insert(id.clone()),
+                                "[Update]: Already assigned"
+                            );
+                        }
+                    }
+                }
+            }
+            // Every escrowed non-fungible must be assigned once
+            for (resource_address, vault) in self.escrow.iter() {
+                let count = assigned.get(resource_address).map_or(0, |ids| ids.len());
+                assert!(
+                    Decimal::from(count) == vault.amount(),
+                    "[Update]: Invalid Sum"
+                );
+            }
+            self.objectives = objectives;
+            self.nft_objectives = nft_objectives;
+
+            // CREATE TXS
+            self.create_tx(
+                self.admin_handle.clone(),
+                self.admin_badge,
+                self.contract_handle.clone(),
+                self.badge_manager.badge(),
+                None,
+                dec!(0),
+                TxType::Update,
+            );
+        }
+
+        fn reward_objective(&mut self, obj_number: Decimal) {
+            let members = self.objectives.remove(&obj_number).unwrap_or_default();
+            let nft_members = self.nft_objectives.remove(&obj_number).unwrap_or_default();
+            assert!(
+                !members.is_empty() || !nft_members.is_empty(),
+                "[Reward]: No objective"
+            );
+            for (member, amounts) in members.iter() {
+                assert!(self.signatures.contains(member), "[Reward]: No signature");
+                let handle = self.member_badges.get(&member).unwrap().to_owned();
+                for (resource_address, amount) in amounts.iter() {
+                    let pay_bucket = self.funds.get_mut(resource_address).unwrap().take_advanced(
+                        amount.clone(),
+                        WithdrawStrategy::Rounded(RoundingMode::ToZero),
+                    );
+                    assert!(!pay_bucket.is_empty(), "[Reward]: No funds");
+
+                    let new_amount = pay_bucket.amount();
+                    self.set_reserved(member, pay_bucket);
+                    // CREATE TXS
+                    self.create_tx(
+                        self.contract_handle.clone(),
+                        self.badge_manager.badge(),
+                        handle.clone(),
+                        member.clone(),
+                        Some(*resource_address),
+                        new_amount,
+                        TxType::Reward,
+                    );
+                }
+            }
+            for (member, nfts) in nft_members.iter() {
+                assert!(self.signatures.contains(member), "[Reward]: No signature");
+                let handle = self.member_badges.get(&member).unwrap().to_owned();
+                for (resource_address, ids) in nfts.iter() {
+                    let pay_bucket = self
+                        .escrow
+                        .get_mut(resource_address)
+                        .unwrap()
+                        .take_non_fungibles(ids);
+
+                    self.set_nft_reserved(member, pay_bucket);
+                    // CREATE TXS
+                    self.create_nft_tx(
+                        self.contract_handle.clone(),
+                        self.badge_manager.badge(),
+                        handle.clone(),
+                        member.clone(),
+                        *resource_address,
+                        ids.clone(),
+                        TxType::Reward,
+                    );
+                }
+            }
+            assert!(
+                !self.completed.contains_key(&obj_number),
+                "[Reward]: Already completed"
+            );
+            self.completed.insert(obj_number, members);
+            self.nft_completed.insert(obj_number, nft_members);
+        }
+
+        fn cancel(&mut self) -> Vec<Bucket> {
+            self.check_list();
+            self.objectives = HashMap::new();
+            self.nft_objectives = HashMap::new();
+            self.is_cancelled = true;
+            self.cancelled_epoch = Self::get_curr_epoch();
+            let mut totals: Vec<Bucket> = Vec::new();
+            for (_, vault) in self.funds.iter_mut() {
+                if !vault.is_empty() {
+                    totals.push(Bucket::from(vault.take_all()));
+                }
+            }
+            let mut nft_totals: Vec<NonFungibleBucket> = Vec::new();
+            for (_, vault) in self.escrow.iter_mut() {
+                if !vault.is_empty() {
+                    nft_totals.push(vault.take_all());
+                }
+            }
+
+            // CREATE TXS
+            for total in totals.iter() {
+                self.create_tx(
+                    self.contract_handle.clone(),
+                    self.badge_manager.badge(),
+                    self.admin_handle.clone(),
+                    self.admin_badge,
+                    Some(total.resource_address()),
+                    total.amount(),
+                    TxType::Cancellation,
+                );
+            }
+            for nft_total in nft_totals {
+                self.create_nft_tx(
+                    self.contract_handle.clone(),
+                    self.badge_manager.badge(),
+                    self.admin_handle.clone(),
+                    self.admin_badge,
+                    nft_total.resource_address(),
+                    nft_total.non_fungible_local_ids(),
+                    TxType::Cancellation,
+                );
+                totals.push(Bucket::from(nft_total));
+            }
+            self.update_listings();
+
+            totals
+        }
+
+        fn remove_member(&mut self, member_badge: ResourceAddress) {
+            // resolve needs the member handle, so keep them until the dispute is settled
+            let is_disputed = self
+                .disputes
+                .values()
+                .any(|members| members.contains_key(&member_badge));
+            assert!(!is_disputed, "[Remove]: Open dispute");
+            for (_, members) in self.objectives.iter_mut() {
+                members.remove(&member_badge);
+            }
+            for (_, members) in self.nft_objectives.iter_mut() {
+                members.remove(&member_badge);
+            }
+
+            let handle = self.member_badges.remove(&member_badge).unwrap();
+            if self.signatures.contains(&member_badge) {
+                self.removed.insert(member_badge, handle.clone());
+            }
+
+            // CREATE TXS
+            self.create_tx(
+                self.admin_handle.clone(),
+                self.admin_badge,
+                handle,
+                member_badge,
+                None,
+                dec!(0),
+                TxType::Remove,
+            );
+            self.update_listings();
+        }
+
+        fn set_details(
+            &mut self,
+            start_epoch: i64,
+            end_epoch: i64,
+            max_members: u8,
+            image: String,
+            details: HashMap<String, String>,
+        ) {
+            assert!(end_epoch >= start_epoch, "[Instantiate]: Invalid Dates");
+            self.start_epoch = start_epoch;
+            self.end_epoch = end_epoch;
+            self.image = Url::of(image);
+            for (key, value) in details.iter() {
+                self.details.insert(key.to_owned(), value.to_owned());
+            }
+            self.max_members = max_members;
+
+            // CREATE TXS
+            self.create_tx(
+                self.admin_handle.clone(),
+                self.admin_badge,
+                self.contract_handle.clone(),
+                self.badge_manager.badge(),
+                None,
+                dec!(0),
+                TxType::Details,
+            );
+            self.update_listings();
+        }
+
+        fn finalize_cancel(&mut self) -> Vec<Bucket> {
+            assert!(!self.is_cancelled, "[Cancellation]: Already Cancelled");
+            assert!(
+                self.cancel_request_epoch.is_some(),
+                "[Cancellation]: Not requested"
+            );
+            assert!(
+                Self::get_curr_epoch() >= self.cancel_request_epoch.unwrap() + self.notice_period,
+                "[Cancellation]: Notice period not over"
+            );
+            self.cancel()
+        }
+
+        fn take_refunds(&mut self) -> Vec<Bucket> {
+            let mut buckets: Vec<Bucket> = Vec::new();
+            for (_, vault) in self.refunds.iter_mut() {
+                if !vault.is_empty() {
+                    buckets.push(Bucket::from(vault.take_all()));
+                }
+            }
+            assert!(!buckets.is_empty(), "[Withdraw Refunds]: Is empty");
+
+            // CREATE TXS
+            for bucket in buckets.iter() {
+                self.create_tx(
+                    self.contract_handle.clone(),
+                    self.badge_manager.badge(),
+                    self.admin_handle.clone(),
+                    self.admin_badge,
+                    Some(bucket.resource_address()),
+                    bucket.amount(),
+                    TxType::Cancellation,
+                );
+            }
+
+            buckets
+        }
+
+        fn request_cancel(&mut self) {
+            self.check_list();
+            assert!(!self.is_cancelled, "[Cancellation]: Already Cancelled");
+            assert!(
+                self.cancel_request_epoch.is_none(),
+                "[Cancellation]: Already requested"
+            );
+            self.cancel_request_epoch = Some(Self::get_curr_epoch());
+
+            // CREATE TXS
+            self.create_tx(
+                self.admin_handle.clone(),
+                self.admin_badge,
+                self.contract_handle.clone(),
+                self.badge_manager.badge(),
+                None,
+                dec!(0),
+                TxType::Notice,
+            );
+            self.update_listings();
+        }
+
+        fn get_multisig(&self) -> &Owned<Multisig> {
+            assert!(self.multisig.is_some(), "[Multisig]: Not set");
+            self.multisig.as_ref().unwrap()
+        }
+
+        fn set_reserved(&mut self, member: &ResourceAddress, pay_bucket: FungibleBucket) {
+            let resource_address = pay_bucket.resource_address();
+            Self::add_amount(&mut self.rewarded, resource_address, pay_bucket.amount());
+            // Deposit into Reserved Vaults
+            if !self.reserved.contains_key(member) {
+                self.reserved.insert(member.clone(), HashMap::new());
+            }
+            let vaults = self.reserved.get_mut(member).unwrap();
+            if !vaults.contains_key(&resource_address) {
+                vaults.insert(resource_address, FungibleVault::with_bucket(pay_bucket));
+            } else {
+                let vault = vaults.get_mut(&resource_address).unwrap();
+                vault.put(pay_bucket);
+            }
+        }
+
+        fn set_refund(&mut self, refund_bucket: FungibleBucket) {
+            let resource_address = refund_bucket.resource_address();
+            if !self.refunds.contains_key(&resource_address) {
+                self.refunds
+                    .insert(resource_address, FungibleVault::with_bucket(refund_bucket));
+            } else {
+                self.refunds
+                    .get_mut(&resource_address)
+                    .unwrap()
+                    .put(refund_bucket);
+            }
+        }
+
+        fn set_nft_reserved(&mut self, member: &ResourceAddress, pay_bucket: NonFungibleBucket) {
+            let resource_address = pay_bucket.resource_address();
+            if !self.nft_reserved.contains_key(member) {
+                self.nft_reserved.insert(member.clone(), HashMap::new());
+            }
+            let vaults = self.nft_reserved.get_mut(member).unwrap();
+            if !vaults.contains_key(&resource_address) {
+                vaults.insert(resource_address, NonFungibleVault::with_bucket(pay_bucket));
+            } else {
+                let vault = vaults.get_mut(&resource_address).unwrap();
+                vault.put(pay_bucket);
+            }
+        }
+
+        fn add_amount(
+            amounts: &mut HashMap<ResourceAddress, Decimal>,
+            resource_address: ResourceAddress,
+            amount: Decimal,
+        ) {
+            let total = Self::get_amount(amounts, &resource_address) + amount;
+            amounts.insert(resource_address, total);
+        }
+
+        fn get_amount(
+            amounts: &HashMap<ResourceAddress, Decimal>,
+            resource_address: &ResourceAddress,
+        ) -> Decimal {
+            amounts.get(resource_address).cloned().unwrap_or(dec!(0))
+        }
+
+        fn update_listings(&self) {
+            let state = if self.is_cancelled {
+                ListingState::Closed
+            } else if !self.is_joinable() {
+                ListingState::Filled
+            } else {
+                ListingState::Active
+            };
+            for marketplace_address in self.marketplaces.iter() {
+                let marketplace = Global::<Marketplace>::from(*marketplace_address);
+                marketplace.update_listing(
+                    Runtime::global_address(),
+                    self.category.clone(),
+                    ContractKind::Project,
+                    state.clone(),
+                );
+            }
+        }
+
+        fn is_joinable(&self) -> bool {
+            self.member_badges.len() < usize::from(self.max_members)
+                && !self.is_cancelled
+                && self.cancel_request_epoch.is_none()
+        }
+
         fn check_list(&self) {
             assert!(
                 Self::get_curr_epoch() >= self.list_epoch + SEC_IN_DAY * LOCK_PERIOD,
@@ -504,6 +1544,7 @@ mod project {
             from_badge: ResourceAddress,
             to_handle: String,
             to_badge: ResourceAddress,
+            resource_address: Option<ResourceAddress>,
             amount: Decimal,
             tx_type: TxType,
         ) {
@@ -513,14 +1554,84 @@ mod project {
                 from_badge,
                 to_handle,
                 to_badge,
+                resource_address,
                 amount,
+                ids: IndexSet::new(),
+                proposal_id: None,
+                tx_type,
+            };
+            emit_tx_event(Runtime::global_address(), &tx_data);
+            self.badge_manager.create_tx(tx_data);
+        }
+
+        fn create_proposal_tx(
+            &self,
+            signer_handle: String,
+            signer_badge: ResourceAddress,
+            proposal_id: Decimal,
+            tx_type: TxType,
+        ) {
+            let tx_data = TxData {
+                epoch: Self::get_curr_epoch(),
+                from_handle: signer_handle,
+                from_badge: signer_badge,
+                to_handle: self.contract_handle.clone(),
+                to_badge: self.badge_manager.badge(),
+                resource_address: None,
+                amount: dec!(0),
+                ids: IndexSet::new(),
+                proposal_id: Some(proposal_id),
+                tx_type,
+            };
+            emit_tx_event(Runtime::global_address(), &tx_data);
+            self.badge_manager.create_tx(tx_data);
+        }
+
+        /// Non-fungibles are recorded by id, they never count towards amounts
+        fn create_nft_tx(
+            &self,
+            from_handle: String,
+            from_badge: ResourceAddress,
+            to_handle: String,
+            to_badge: ResourceAddress,
+            resource_address: ResourceAddress,
+            ids: IndexSet<NonFungibleLocalId>,
+            tx_type: TxType,
+        ) {
+            let tx_data = TxData {
+                epoch: Self::get_curr_epoch(),
+                from_handle,
+                from_badge,
+                to_handle,
+                to_badge,
+                resource_address: Some(resource_address),
+                amount: dec!(0),
+                ids,
+                proposal_id: None,
                 tx_type,
             };
+            emit_tx_event(Runtime::global_address(), &tx_data);
             self.badge_manager.create_tx(tx_data);
         }
 
+        /// Accepts a proof of the Member badge or of the non-transferable member nft of this contract
         fn check_proof(&self, member_badge: &ResourceAddress, proof: NonFungibleProof) -> String {
-            let handle = Self::get_proof_id(member_badge, proof);
+            let contract_badge = self.badge_manager.member_badge();
+            if proof.resource_address() == contract_badge {
+                let handle = Self::get_proof_id(&contract_badge, proof);
+                // handles aren't unique across Member badges, the nft id can only be minted once
+                // so it's bound to the signed member that joined with it
+                let joined_badge = self
+                    .signatures
+                    .iter()
+                    .find(|badge| self.member_badges.get(*badge) == Some(&handle));
+                assert!(
+                    joined_badge == Some(member_badge),
+                    "[Check Proof]: Not Equal"
+                );
+                return handle;
+            }
+            let handle = Self::get_member_handle(member_badge, proof);
             let saved_handle = self.member_badges.get(&member_badge).unwrap();
             assert!(&handle == saved_handle, "[Check Proof]: Not Equal");
             handle
@@ -535,6 +1646,23 @@ mod project {
             string_id.value().to_owned()
         }
 
+        /// Member badges are resolved by their metadata, so a recovered badge keeps the handle
+        fn get_member_handle(badge: &ResourceAddress, proof: NonFungibleProof) -> String {
+            let manager = ResourceManager::from(*badge);
+            let handle: Option<String> = manager.get_metadata(MEMBER_HANDLE).unwrap_or(None);
+            let badge_id: Option<String> = manager.get_metadata(BADGE_ID).unwrap_or(None);
+            let id = Self::get_proof_id(badge, proof);
+            match handle {
+                // the id of a lost badge is no longer valid once replaced
+                Some(handle) => {
+                    let valid_id = badge_id.unwrap_or(handle.clone());
+                    assert!(id == valid_id, "[Check Proof]: Replaced badge");
+                    handle
+                }
+                None => id,
+            }
+        }
+
         fn check_funds(funds: &FungibleBucket) {
             let resource = ResourceManager::from_address(funds.resource_address()).resource_type();
             assert!(
@@ -0,0 +1,114 @@
+use crate::types::Balances;
+use scrypto::prelude::*;
+
+#[blueprint]
+mod treasury {
+
+    struct Treasury {
+        resources: KeyValueStore<ResourceAddress, Vault>,
+        resource_addresses: Vec<ResourceAddress>,
+    }
+
+    impl Treasury {
+        pub fn new() -> Owned<Treasury> {
+            Self {
+                resources: KeyValueStore::new(),
+                resource_addresses: Vec::new(),
+            }
+            .instantiate()
+        }
+
+        pub fn deposit(&mut self, bucket: Bucket) {
+            let resource_address = bucket.resource_address();
+            let has_resource = self.resources.get(&resource_address).is_some();
+            if has_resource {
+                self.resources
+                    .get_mut(&resource_address)
+                    .unwrap()
+                    .put(bucket);
+            } else {
+                self.resources
+                    .insert(resource_address, Vault::with_bucket(bucket));
+                self.resource_addresses.push(resource_address);
+            }
+        }
+
+        pub fn withdraw(&mut self, resource_address: ResourceAddress) -> Bucket {
+            self.check_resource(&resource_address);
+            self.resources
+                .get_mut(&resource_address)
+                .unwrap()
+                .take_all()
+        }
+
+        pub fn withdraw_amount(
+            &mut self,
+            resource_address: ResourceAddress,
+            amount: Decimal,
+        ) -> Bucket {
+            self.check_resource(&resource_address);
+            let mut vault = self.resources.get_mut(&resource_address).unwrap();
+            assert!(
+                amount > dec!(0) && amount <= vault.amount(),
+                "[Withdraw]: Invalid amount"
+            );
+            vault.take(amount)
+        }
+
+        pub fn withdraw_ids(
+            &mut self,
+            resource_address: ResourceAddress,
+            ids: IndexSet<NonFungibleLocalId>,
+        ) -> Bucket {
+            self.check_resource(&resource_address);
+            assert!(
+                !resource_address.is_fungible(),
+                "[Withdraw]: Not a non-fungible resource"
+            );
+            let vault = self.resources.get_mut(&resource_address).unwrap();
+            let mut vault = vault.as_non_fungible();
+            for id in ids.iter() {
+                assert!(vault.contains_non_fungible(id), "[Withdraw]: Id not found");
+            }
+            Bucket::from(vault.take_non_fungibles(&ids))
+        }
+
+        /// Creates a proof of a held non-fungible without it leaving the vault
+        pub fn create_proof(
+            &self,
+            resource_address: ResourceAddress,
+            id: NonFungibleLocalId,
+        ) -> NonFungibleProof {
+            self.check_resource(&resource_address);
+            let vault = self.resources.get(&resource_address).unwrap();
+            let vault = vault.as_non_fungible();
+            assert!(vault.contains_non_fungible(&id), "[Proof]: Id not found");
+            vault.create_proof_of_non_fungibles(&indexset!(id))
+        }
+
+        pub fn balances(&self) -> Balances {
+            let mut fungibles = HashMap::new();
+            let mut non_fungibles = HashMap::new();
+            for resource_address in self.resource_addresses.iter() {
+                let vault = self.resources.get(resource_address).unwrap();
+                if resource_address.is_fungible() {
+                    fungibles.insert(*resource_address, vault.amount());
+                } else {
+                    let ids = vault.as_non_fungible().non_fungible_local_ids(u32::MAX);
+                    non_fungibles.insert(*resource_address, ids);
+                }
+            }
+            Balances {
+                fungibles,
+                non_fungibles,
+            }
+        }
+
+        fn check_resource(&self, resource_address: &ResourceAddress) {
+            assert!(
+                self.resources.get(resource_address).is_some(),
+                "[Withdraw]: Resource not found"
+            );
+        }
+    }
+}
@@ -1,3 +1,4 @@
+use crate::vesting_schedule::{VestingCurve, VestingUnit};
 use scrypto::prelude::*;
 
 pub const INFO_URL: &str = "https://github.com/diamondpay";
@@ -5,14 +6,39 @@ pub const ICON_URL: &str = "https://avatars.githubusercontent.com/u/162780104";
 pub const SEC_IN_DAY: i64 = 60i64 * 60i64 * 24i64;
 pub const MAX_MEMBERS: usize = 10;
 pub const MAX_OBJS: usize = 30;
+pub const MAX_RESOURCES: usize = 5;
+pub const MAX_APPLICATIONS: usize = 30;
+pub const MAX_PAGE: u64 = 100;
 pub const LOCK_PERIOD: i64 = 5;
 pub const MEMBER_ADDRESS: &str = "member_address";
+pub const MEMBER_HANDLE: &str = "member_handle";
+// id of a recovered member badge, set on the badge resource once the lost badge is replaced
+pub const BADGE_ID: &str = "badge_id";
 
-#[derive(ScryptoSbor, PartialEq, Clone)]
+#[derive(ScryptoSbor, ManifestSbor, Clone, Debug, PartialEq)]
 pub enum ContractKind {
     Project,
     Job,
 }
+#[derive(ScryptoSbor, ManifestSbor, Clone, Debug, PartialEq)]
+pub enum ListingFee {
+    // fixed fee for every contract
+    Flat(Decimal),
+    // percentage of the contract amount, eg. 1 for 1%, with optional caps
+    Percent {
+        percent: Decimal,
+        min: Option<Decimal>,
+        max: Option<Decimal>,
+    },
+}
+
+#[derive(ScryptoSbor, PartialEq, Clone)]
+pub enum ListingState {
+    Active,
+    Filled,
+    Closed,
+}
+
 #[derive(ScryptoSbor, PartialEq)]
 pub enum ContractRole {
     Admin,
@@ -34,6 +60,22 @@ pub struct MemberData {
     pub member_address: ComponentAddress,
 }
 
+#[derive(ScryptoSbor, Clone)]
+pub struct RecoveryData {
+    // account each guardian approves, guardians can change theirs by approving again
+    pub approvals: HashMap<ResourceAddress, ComponentAddress>,
+    // account the recovered badge is sent to, set once its approvals reach the threshold
+    pub account: Option<ComponentAddress>,
+    pub epoch: Option<Decimal>,
+}
+
+#[derive(ScryptoSbor, Clone, Debug, PartialEq)]
+pub struct Balances {
+    pub fungibles: HashMap<ResourceAddress, Decimal>,
+    // held ids by resource, non-fungibles are never counted as amounts
+    pub non_fungibles: HashMap<ResourceAddress, IndexSet<NonFungibleLocalId>>,
+}
+
 #[derive(ScryptoSbor)]
 pub struct TeamData {
     // name, icon_url, team_handle, subtitle, description
@@ -41,7 +83,7 @@ pub struct TeamData {
     pub details: HashMap<String, String>,
 }
 
-#[derive(ScryptoSbor, Clone)]
+#[derive(ScryptoSbor, ManifestSbor, PartialEq, Clone)]
 pub enum TxType {
     Create,
     Details,
@@ -55,6 +97,114 @@ pub enum TxType {
     Reward,
     Withdraw,
     Cancellation,
+    Dispute,
+    Resolve,
+    Pause,
+    Resume,
+    Delist,
+    Apply,
+    Reject,
+    // multisig txs, recorded with their proposal id
+    Propose,
+    Approve,
+    Execute,
+    // cancellation requested, the notice period has started
+    Notice,
+    // admin's share of a resolved dispute
+    Refund,
+}
+
+#[derive(ScryptoSbor, Clone)]
+pub struct ApplicationData {
+    pub handle: String,
+    pub epoch: Decimal,
+    // message, contact_urls, link_urls
+    pub details: HashMap<String, String>,
+}
+
+// Admin actions of a Project that require multisig approval once signers are set.
+// Deposits can't be proposed, signers deposit directly instead.
+#[derive(ScryptoSbor, ManifestSbor, Clone, Debug, PartialEq)]
+pub enum ProjectAction {
+    Invite(ResourceAddress, String),
+    AcceptApplication(ResourceAddress),
+    RejectApplication(ResourceAddress),
+    ClearApplications,
+    Update(
+        HashMap<Decimal, HashMap<ResourceAddress, HashMap<ResourceAddress, Decimal>>>,
+        HashMap<
+            Decimal,
+            HashMap<ResourceAddress, HashMap<ResourceAddress, IndexSet<NonFungibleLocalId>>>,
+        >,
+    ),
+    Reward(Decimal),
+    // start, end, max members, image, details
+    Details(i64, i64, u8, String, HashMap<String, String>),
+    WithdrawRefunds,
+    Remove(ResourceAddress),
+    List(ComponentAddress),
+    Cancellation,
+    FinalizeCancellation,
+    // signers & threshold replacing the current ones
+    SetSigners(Vec<ResourceAddress>, u64),
+}
+
+// Admin actions of a Job that require multisig approval once signers are set
+#[derive(ScryptoSbor, ManifestSbor, Clone, Debug, PartialEq)]
+pub enum JobAction {
+    Invite(ResourceAddress, String),
+    // member badge, handle & the vesting schedule
+    InviteMember(
+        ResourceAddress,
+        String,
+        i64,
+        Option<i64>,
+        i64,
+        i64,
+        VestingUnit,
+        VestingCurve,
+        Decimal,
+    ),
+    AcceptApplication(ResourceAddress),
+    // member badge & the vesting schedule
+    AcceptMemberApplication(
+        ResourceAddress,
+        i64,
+        Option<i64>,
+        i64,
+        i64,
+        VestingUnit,
+        VestingCurve,
+        Decimal,
+    ),
+    RejectApplication(ResourceAddress),
+    ClearApplications,
+    Pause(ResourceAddress),
+    Resume(ResourceAddress),
+    // image, details
+    Details(String, HashMap<String, String>),
+    Remove(ResourceAddress),
+    List(ComponentAddress),
+    Cancellation,
+    FinalizeCancellation,
+    // signers & threshold replacing the current ones
+    SetSigners(Vec<ResourceAddress>, u64),
+}
+
+// Proposals of a Multisig, owned by either a Project or a Job
+#[derive(ScryptoSbor, ManifestSbor, Clone, Debug, PartialEq)]
+pub enum AdminAction {
+    Project(ProjectAction),
+    Job(JobAction),
+}
+
+#[derive(ScryptoSbor, Clone)]
+pub struct ProposalData {
+    pub action: AdminAction,
+    pub proposer: ResourceAddress,
+    pub approvals: HashSet<ResourceAddress>,
+    pub epoch: Decimal,
+    pub is_executed: bool,
 }
 
 #[derive(NonFungibleData, ScryptoSbor, Clone)]
@@ -64,6 +214,103 @@ pub struct TxData {
     pub from_badge: ResourceAddress,
     pub to_handle: String,
     pub to_badge: ResourceAddress,
+    // resource moved by the tx, none for txs that only record an action
+    pub resource_address: Option<ResourceAddress>,
+    // fungible amount, non-fungibles are recorded by id with a zero amount
     pub amount: Decimal,
+    pub ids: IndexSet<NonFungibleLocalId>,
+    // proposal of a Propose, Approve or Execute tx
+    pub proposal_id: Option<Decimal>,
     pub tx_type: TxType,
 }
+
+#[derive(ScryptoSbor, Clone)]
+pub struct YearData {
+    // range of tx ids created in the year
+    pub first_tx: Decimal,
+    pub last_tx: Decimal,
+    // totals of tx amounts in the year by resource
+    pub deposited: HashMap<ResourceAddress, Decimal>,
+    pub rewarded: HashMap<ResourceAddress, Decimal>,
+    pub withdrawn: HashMap<ResourceAddress, Decimal>,
+    pub cancelled: HashMap<ResourceAddress, Decimal>,
+}
+
+// Each TxType has a dedicated event carrying the TxData fields & the contract address
+macro_rules! tx_events {
+    ($($tx_type:ident => $event:ident),* $(,)?) => {
+        $(
+            #[derive(ScryptoSbor, ScryptoEvent, Clone)]
+            pub struct $event {
+                pub contract_address: ComponentAddress,
+                pub epoch: Decimal,
+                pub from_handle: String,
+                pub from_badge: ResourceAddress,
+                pub to_handle: String,
+                pub to_badge: ResourceAddress,
+                pub resource_address: Option<ResourceAddress>,
+                pub amount: Decimal,
+                pub ids: IndexSet<NonFungibleLocalId>,
+                pub proposal_id: Option<Decimal>,
+            }
+        )*
+
+        pub fn emit_tx_event(contract_address: ComponentAddress, tx_data: &TxData) {
+            let TxData {
+                epoch,
+                from_handle,
+                from_badge,
+                to_handle,
+                to_badge,
+                resource_address,
+                amount,
+                ids,
+                proposal_id,
+                tx_type,
+            } = tx_data.clone();
+            match tx_type {
+                $(
+                    TxType::$tx_type => Runtime::emit_event($event {
+                        contract_address,
+                        epoch,
+                        from_handle,
+                        from_badge,
+                        to_handle,
+                        to_badge,
+                        resource_address,
+                        amount,
+                        ids,
+                        proposal_id,
+                    }),
+                )*
+            }
+        }
+    };
+}
+
+tx_events! {
+    Create => CreateEvent,
+    Details => DetailsEvent,
+    Deposit => DepositEvent,
+    List => ListEvent,
+    Invite => InviteEvent,
+    Remove => RemoveEvent,
+    Leave => LeaveEvent,
+    Update => UpdateEvent,
+    Join => JoinEvent,
+    Reward => RewardEvent,
+    Withdraw => WithdrawEvent,
+    Cancellation => CancellationEvent,
+    Dispute => DisputeEvent,
+    Resolve => ResolveEvent,
+    Pause => PauseEvent,
+    Resume => ResumeEvent,
+    Delist => DelistEvent,
+    Apply => ApplyEvent,
+    Reject => RejectEvent,
+    Propose => ProposeEvent,
+    Approve => ApproveEvent,
+    Execute => ExecuteEvent,
+    Notice => NoticeEvent,
+    Refund => RefundEvent,
+}
@@ -1,15 +1,50 @@
 use crate::types::SEC_IN_DAY;
 use scrypto::prelude::*;
 
+#[derive(ScryptoSbor, ManifestSbor, Clone, Debug, PartialEq)]
+pub enum VestingCurve {
+    // vests equally every interval
+    Linear,
+    // vests the full amount at the cliff
+    CliffLumpSum,
+    // vests a percentage every interval, eg. 10/20/30/40
+    Graded(Vec<Decimal>),
+    // vests by elapsed time to the power of an exponent, eg. 2 for quadratic
+    BackLoaded(u8),
+}
+
+#[derive(ScryptoSbor, ManifestSbor, Clone, Debug, PartialEq)]
+pub enum VestingUnit {
+    Days,
+    Weeks,
+    // calendar months, vesting on the same day of the month as the start
+    Months,
+}
+
+// paid to the member when the admin cancels, not when the member leaves
+#[derive(ScryptoSbor, ManifestSbor, Clone, Debug, PartialEq)]
+pub enum Severance {
+    // vesting continues for extra intervals after the cancellation
+    Intervals(u32),
+    // fixed amount on top of the vested amount
+    Amount(Decimal),
+}
+
 #[derive(ScryptoSbor)]
 pub struct VestingSchedule {
     pub start_epoch: i64,
     pub cliff_epoch: Option<i64>,
     pub end_epoch: i64,
     pub vest_interval: i64,
+    pub vest_unit: VestingUnit,
+    pub vest_curve: VestingCurve,
     pub amount: Decimal,
     pub withdrawn: Decimal,
+    // paid on cancellation, owed on top of the vested amount
+    pub severance: Decimal,
     pub cancel_epoch: Option<i64>,
+    pub pause_epoch: Option<i64>,
+    pub paused_time: i64,
     pub is_check_join: bool,
 }
 
@@ -19,19 +54,33 @@ impl VestingSchedule {
         cliff_epoch: Option<i64>,
         end_epoch: i64,
         vest_interval: i64,
+        vest_unit: VestingUnit,
+        vest_curve: VestingCurve,
         amount: Decimal,
         is_check_join: bool,
     ) -> Self {
-        Self::check_schedule(&start_epoch, &cliff_epoch, &end_epoch, &vest_interval);
+        Self::check_schedule(
+            &start_epoch,
+            &cliff_epoch,
+            &end_epoch,
+            &vest_interval,
+            &vest_unit,
+            &vest_curve,
+        );
 
         Self {
             start_epoch,
             cliff_epoch,
             end_epoch,
             vest_interval,
+            vest_unit,
+            vest_curve,
             amount,
             withdrawn: dec!(0),
+            severance: dec!(0),
             cancel_epoch: None,
+            pause_epoch: None,
+            paused_time: 0i64,
             is_check_join,
         }
     }
@@ -41,6 +90,25 @@ impl VestingSchedule {
             Some(v) => v,
             None => Self::get_curr_epoch(),
         };
+        self.get_vested_at(curr_epoch)
+    }
+
+    /// Vested amount plus any severance paid on cancellation
+    pub fn get_owed(&self) -> Decimal {
+        self.get_vested() + self.severance
+    }
+
+    pub fn get_vested_at(&self, curr_epoch: i64) -> Decimal {
+        // paused time doesn't vest & pushes back the remaining schedule
+        let curr_epoch = match self.pause_epoch {
+            Some(p_epoch) => curr_epoch.min(p_epoch),
+            None => curr_epoch,
+        } - self.paused_time;
+        self.get_unpaused_vested_at(curr_epoch)
+    }
+
+    /// Vested amount at an epoch that already excludes the paused time
+    fn get_unpaused_vested_at(&self, curr_epoch: i64) -> Decimal {
         let cutoff_epoch = match self.cliff_epoch {
             Some(c_epoch) => c_epoch,
             None => self.start_epoch,
@@ -48,27 +116,123 @@ impl VestingSchedule {
         if curr_epoch >= self.end_epoch {
             return self.amount;
         }
+        if self.vest_curve == VestingCurve::CliffLumpSum {
+            return if curr_epoch >= cutoff_epoch {
+                self.amount
+            } else {
+                dec!("0")
+            };
+        }
         if curr_epoch <= cutoff_epoch {
             return dec!("0");
         }
 
         // vest in intervals, eg. every 7 days
-        let interval = self.vest_interval * SEC_IN_DAY;
-        let elapsed_time = curr_epoch - self.start_epoch;
+        // interval & vest time are in seconds, or in months for calendar months
+        let (interval, elapsed_time, vest_time) = match self.vest_unit {
+            VestingUnit::Days => (
+                self.vest_interval * SEC_IN_DAY,
+                curr_epoch - self.start_epoch,
+                self.end_epoch - self.start_epoch,
+            ),
+            VestingUnit::Weeks => (
+                self.vest_interval * SEC_IN_DAY * 7i64,
+                curr_epoch - self.start_epoch,
+                self.end_epoch - self.start_epoch,
+            ),
+            VestingUnit::Months => (
+                self.vest_interval,
+                Self::get_elapsed_months(self.start_epoch, curr_epoch),
+                Self::get_elapsed_months(self.start_epoch, self.end_epoch),
+            ),
+        };
         let elapsed_intervals: i64 = elapsed_time / interval; // integer division round down
 
-        // divide total amount by total time & multiply by interval
-        let vest_time = self.end_epoch - self.start_epoch;
-        let vest_per_interval: Decimal = (self.amount / vest_time) * interval; // divide before multiply
+        match &self.vest_curve {
+            VestingCurve::Graded(percentages) => {
+                // sum the percentages of every completed interval
+                let periods = usize::try_from(elapsed_intervals).unwrap_or(0);
+                let percent = percentages
+                    .iter()
+                    .take(periods)
+                    .fold(dec!(0), |total, p| total + *p);
+                self.amount * percent / dec!(100)
+            }
+            VestingCurve::BackLoaded(exponent) => {
+                let elapsed_ratio =
+                    (Decimal::from(elapsed_intervals * interval) / vest_time).min(dec!(1));
+                let mut ratio = dec!(1);
+                for _ in 0..*exponent {
+                    ratio = ratio * elapsed_ratio;
+                }
+                self.amount * ratio
+            }
+            _ => {
+                // divide total amount by total time & multiply by interval
+                let vest_per_interval: Decimal = (self.amount / vest_time) * interval; // divide before multiply
 
-        let total_vested: Decimal = vest_per_interval * elapsed_intervals;
-        total_vested
+                let total_vested: Decimal = vest_per_interval * elapsed_intervals;
+                total_vested
+            }
+        }
+    }
+
+    /// Amount owed on cancellation, capped at the unvested amount
+    pub fn get_severance(&self, severance: &Severance) -> Decimal {
+        let cancel_epoch = match self.cancel_epoch {
+            Some(v) => v,
+            None => Self::get_curr_epoch(),
+        };
+        let vested = self.get_vested_at(cancel_epoch);
+        let amount = match severance {
+            Severance::Intervals(intervals) => {
+                let intervals = i64::from(*intervals) * self.vest_interval;
+                let severance_epoch = match self.vest_unit {
+                    VestingUnit::Days => cancel_epoch + intervals * SEC_IN_DAY,
+                    VestingUnit::Weeks => cancel_epoch + intervals * SEC_IN_DAY * 7i64,
+                    VestingUnit::Months => {
+                        let cancel_date =
+                            UtcDateTime::from_instant(&Instant::new(cancel_epoch)).unwrap();
+                        Self::add_months(&cancel_date, intervals)
+                    }
+                };
+                // a paused schedule keeps vesting after the cancellation as if resumed
+                let paused_time = match self.pause_epoch {
+                    Some(p_epoch) => self.paused_time + cancel_epoch - p_epoch,
+                    None => self.paused_time,
+                };
+                self.get_unpaused_vested_at(severance_epoch - paused_time) - vested
+            }
+            Severance::Amount(amount) => *amount,
+        };
+        amount.min(self.amount - vested)
     }
 
     pub fn get_unvested(&self) -> Decimal {
         self.amount - self.get_vested()
     }
 
+    pub fn get_end_epoch(&self) -> i64 {
+        let pausing_time = match self.pause_epoch {
+            Some(p_epoch) => Self::get_curr_epoch() - p_epoch,
+            None => 0i64,
+        };
+        self.end_epoch + self.paused_time + pausing_time
+    }
+
+    pub fn pause(&mut self) {
+        assert!(self.cancel_epoch.is_none(), "[Pause]: Is Cancelled");
+        assert!(self.pause_epoch.is_none(), "[Pause]: Already Paused");
+        self.pause_epoch = Some(Self::get_curr_epoch());
+    }
+
+    pub fn resume(&mut self) {
+        assert!(self.cancel_epoch.is_none(), "[Resume]: Is Cancelled");
+        assert!(self.pause_epoch.is_some(), "[Resume]: Not Paused");
+        let p_epoch = self.pause_epoch.take().unwrap();
+        self.paused_time = self.paused_time + Self::get_curr_epoch() - p_epoch;
+    }
+
     pub fn check_join(&self) {
         if self.is_check_join {
             assert!(
@@ -83,6 +247,8 @@ impl VestingSchedule {
         cliff_epoch: &Option<i64>,
         end_epoch: &i64,
         vest_interval: &i64,
+        vest_unit: &VestingUnit,
+        vest_curve: &VestingCurve,
     ) {
         match cliff_epoch {
             Some(c_epoch) => assert!(
@@ -95,9 +261,300 @@ impl VestingSchedule {
             ),
         };
         assert!(vest_interval > &0i64, "[Check Schedule]: No Interval");
+        if vest_unit == &VestingUnit::Months {
+            let months = Self::get_elapsed_months(*start_epoch, *end_epoch);
+            assert!(months > 0i64, "[Check Schedule]: Must be at least a month");
+            // a partial last month would vest fully at the last whole month
+            let start = UtcDateTime::from_instant(&Instant::new(*start_epoch)).unwrap();
+            assert!(
+                Self::add_months(&start, months) == *end_epoch,
+                "[Check Schedule]: Must be whole months"
+            );
+        }
+
+        match vest_curve {
+            VestingCurve::Linear => {}
+            VestingCurve::CliffLumpSum => assert!(
+                cliff_epoch.is_some(),
+                "[Check Schedule]: Lump sum must have a cliff"
+            ),
+            VestingCurve::Graded(percentages) => {
+                assert!(!percentages.is_empty(), "[Check Schedule]: No Percentages");
+                let mut total = dec!(0);
+                for percent in percentages.iter() {
+                    assert!(percent > &dec!(0), "[Check Schedule]: Invalid Percentage");
+                    total = total + *percent;
+                }
+                assert!(total == dec!(100), "[Check Schedule]: Must total 100");
+                let intervals =
+                    Self::get_interval_count(*start_epoch, *end_epoch, *vest_interval, vest_unit);
+                assert!(
+                    i64::try_from(percentages.len()).unwrap() == intervals,
+                    "[Check Schedule]: Must have a percentage for every interval"
+                );
+            }
+            VestingCurve::BackLoaded(exponent) => assert!(
+                exponent > &1u8,
+                "[Check Schedule]: Exponent must be greater than 1"
+            ),
+        }
     }
 
     pub fn get_curr_epoch() -> i64 {
         Clock::current_time(TimePrecision::Second).seconds_since_unix_epoch
     }
+
+    /// Counts the intervals between two epochs, a partial last interval vests at the end
+    fn get_interval_count(
+        start_epoch: i64,
+        end_epoch: i64,
+        vest_interval: i64,
+        vest_unit: &VestingUnit,
+    ) -> i64 {
+        let (interval, vest_time) = match vest_unit {
+            VestingUnit::Days => (vest_interval * SEC_IN_DAY, end_epoch - start_epoch),
+            VestingUnit::Weeks => (vest_interval * SEC_IN_DAY * 7i64, end_epoch - start_epoch),
+            VestingUnit::Months => (
+                vest_interval,
+                Self::get_elapsed_months(start_epoch, end_epoch),
+            ),
+        };
+        (vest_time + interval - 1i64) / interval // integer division round up
+    }
+
+    /// Counts the whole calendar months between two epochs
+    ///
+    /// A month has passed once the start's day of the month is reached,
+    /// or the last day of the month if it's shorter, eg. Jan 31 -> Feb 29
+    ///
+    fn get_elapsed_months(start_epoch: i64, curr_epoch: i64) -> i64 {
+        if curr_epoch <= start_epoch {
+            return 0i64;
+        }
+        let start = UtcDateTime::from_instant(&Instant::new(start_epoch)).unwrap();
+        let curr = UtcDateTime::from_instant(&Instant::new(curr_epoch)).unwrap();
+        let mut months = (i64::from(curr.year()) - i64::from(start.year())) * 12i64
+            + (i64::from(curr.month()) - i64::from(start.month()));
+        if months > 0i64 && curr_epoch < Self::add_months(&start, months) {
+            months = months - 1i64;
+        }
+        months
+    }
+
+    fn add_months(date: &UtcDateTime, months: i64) -> i64 {
+        let total_months = i64::from(date.year()) * 12i64 + i64::from(date.month()) - 1i64 + months;
+        let year = u32::try_from(total_months / 12i64).unwrap();
+        let month = u8::try_from(total_months % 12i64 + 1i64).unwrap();
+        let day = date.day_of_month().min(Self::days_in_month(year, month));
+        UtcDateTime::new(year, month, day, date.hour(), date.minute(), date.second())
+            .unwrap()
+            .to_instant()
+            .seconds_since_unix_epoch
+    }
+
+    fn days_in_month(year: u32, month: u8) -> u8 {
+        match month {
+            2 => {
+                let is_leap = (year % 4 == 0 && year % 100 != 0) || year % 400 == 0;
+                if is_leap {
+                    29
+                } else {
+                    28
+                }
+            }
+            4 | 6 | 9 | 11 => 30,
+            _ => 31,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const START: i64 = 1_700_000_000i64;
+    const INTERVAL: i64 = 7i64;
+
+    fn schedule(cliff_epoch: Option<i64>, vest_curve: VestingCurve) -> VestingSchedule {
+        let end_epoch = START + 4 * INTERVAL * SEC_IN_DAY;
+        VestingSchedule::new(
+            START,
+            cliff_epoch,
+            end_epoch,
+            INTERVAL,
+            VestingUnit::Days,
+            vest_curve,
+            dec!(2419200),
+            false,
+        )
+    }
+
+    fn epoch_at(intervals: i64) -> i64 {
+        START + intervals * INTERVAL * SEC_IN_DAY
+    }
+
+    #[test]
+    fn test_linear() {
+        let s = schedule(None, VestingCurve::Linear);
+        assert_eq!(s.get_vested_at(START), dec!(0));
+        assert_eq!(s.get_vested_at(epoch_at(1) - 1), dec!(0));
+        assert_eq!(s.get_vested_at(epoch_at(1)), dec!(604800));
+        assert_eq!(s.get_vested_at(epoch_at(3)), dec!(1814400));
+        assert_eq!(s.get_vested_at(epoch_at(4)), dec!(2419200));
+    }
+
+    #[test]
+    fn test_severance() {
+        let mut s = schedule(None, VestingCurve::Linear);
+        s.cancel_epoch = Some(epoch_at(1));
+        assert_eq!(s.get_severance(&Severance::Intervals(2)), dec!(1209600));
+        assert_eq!(s.get_severance(&Severance::Intervals(10)), dec!(1814400));
+        assert_eq!(s.get_severance(&Severance::Amount(dec!(1000))), dec!(1000));
+        assert_eq!(
+            s.get_severance(&Severance::Amount(dec!(5000000))),
+            dec!(1814400)
+        );
+    }
+
+    #[test]
+    fn test_severance_paused() {
+        let mut s = schedule(None, VestingCurve::Linear);
+        // paused after the first interval & cancelled an interval later
+        s.pause_epoch = Some(epoch_at(1));
+        s.cancel_epoch = Some(epoch_at(2));
+        assert_eq!(s.get_vested(), dec!(604800));
+        assert_eq!(s.get_severance(&Severance::Intervals(2)), dec!(1209600));
+        assert_eq!(s.get_severance(&Severance::Intervals(10)), dec!(1814400));
+    }
+
+    #[test]
+    fn test_cliff_lump_sum() {
+        let s = schedule(Some(epoch_at(2)), VestingCurve::CliffLumpSum);
+        assert_eq!(s.get_vested_at(START), dec!(0));
+        assert_eq!(s.get_vested_at(epoch_at(2) - 1), dec!(0));
+        assert_eq!(s.get_vested_at(epoch_at(2)), dec!(2419200));
+        assert_eq!(s.get_vested_at(epoch_at(4)), dec!(2419200));
+    }
+
+    #[test]
+    fn test_graded() {
+        let percentages = vec![dec!(10), dec!(20), dec!(30), dec!(40)];
+        let s = schedule(None, VestingCurve::Graded(percentages));
+        assert_eq!(s.get_vested_at(epoch_at(1) - 1), dec!(0));
+        assert_eq!(s.get_vested_at(epoch_at(1)), dec!(241920));
+        assert_eq!(s.get_vested_at(epoch_at(2)), dec!(725760));
+        assert_eq!(s.get_vested_at(epoch_at(3) - 1), dec!(725760));
+        assert_eq!(s.get_vested_at(epoch_at(3)), dec!(1451520));
+        assert_eq!(s.get_vested_at(epoch_at(4)), dec!(2419200));
+    }
+
+    #[test]
+    fn test_back_loaded() {
+        let s = schedule(None, VestingCurve::BackLoaded(2u8));
+        assert_eq!(s.get_vested_at(epoch_at(1) - 1), dec!(0));
+        assert_eq!(s.get_vested_at(epoch_at(1)), dec!(151200));
+        assert_eq!(s.get_vested_at(epoch_at(2)), dec!(604800));
+        assert_eq!(s.get_vested_at(epoch_at(3)), dec!(1360800));
+        assert_eq!(s.get_vested_at(epoch_at(4)), dec!(2419200));
+    }
+
+    #[test]
+    fn test_weeks() {
+        let end_epoch = START + 4 * INTERVAL * SEC_IN_DAY;
+        let s = VestingSchedule::new(
+            START,
+            None,
+            end_epoch,
+            1i64,
+            VestingUnit::Weeks,
+            VestingCurve::Linear,
+            dec!(2419200),
+            false,
+        );
+        assert_eq!(s.get_vested_at(epoch_at(1) - 1), dec!(0));
+        assert_eq!(s.get_vested_at(epoch_at(1)), dec!(604800));
+        assert_eq!(s.get_vested_at(epoch_at(2)), dec!(1209600));
+    }
+
+    #[test]
+    fn test_months() {
+        // Jan 31 2024 -> Jan 31 2025, vesting on the last day of shorter months
+        let s = VestingSchedule::new(
+            1706659200i64,
+            None,
+            1738281600i64,
+            1i64,
+            VestingUnit::Months,
+            VestingCurve::Linear,
+            dec!(1200),
+            false,
+        );
+        // Feb 29 2024
+        assert_eq!(s.get_vested_at(1709164800i64 - 1), dec!(0));
+        assert_eq!(s.get_vested_at(1709164800i64), dec!(100));
+        // Mar 31 2024
+        assert_eq!(s.get_vested_at(1711843200i64 - 1), dec!(100));
+        assert_eq!(s.get_vested_at(1711843200i64), dec!(200));
+        // Apr 30 2024
+        assert_eq!(s.get_vested_at(1714435200i64), dec!(300));
+        assert_eq!(s.get_vested_at(1738281600i64), dec!(1200));
+    }
+
+    #[test]
+    #[should_panic(expected = "Must be whole months")]
+    fn test_months_partial() {
+        // Jan 31 2024 -> Feb 15 2025
+        VestingSchedule::new(
+            1706659200i64,
+            None,
+            1739577600i64,
+            1i64,
+            VestingUnit::Months,
+            VestingCurve::Linear,
+            dec!(1200),
+            false,
+        );
+    }
+
+    #[test]
+    fn test_paused() {
+        let mut s = schedule(None, VestingCurve::Linear);
+        // paused after the first interval
+        s.pause_epoch = Some(epoch_at(1));
+        assert_eq!(s.get_vested_at(epoch_at(1)), dec!(604800));
+        assert_eq!(s.get_vested_at(epoch_at(3)), dec!(604800));
+
+        // resumed after being paused for one interval
+        s.pause_epoch = None;
+        s.paused_time = INTERVAL * SEC_IN_DAY;
+        assert_eq!(s.get_vested_at(epoch_at(2)), dec!(604800));
+        assert_eq!(s.get_vested_at(epoch_at(3) - 1), dec!(604800));
+        assert_eq!(s.get_vested_at(epoch_at(3)), dec!(1209600));
+        assert_eq!(s.get_vested_at(epoch_at(4)), dec!(1814400));
+        assert_eq!(s.get_vested_at(epoch_at(5)), dec!(2419200));
+    }
+
+    #[test]
+    #[should_panic(expected = "Must total 100")]
+    fn test_graded_invalid_total() {
+        schedule(None, VestingCurve::Graded(vec![dec!(10), dec!(20)]));
+    }
+
+    #[test]
+    #[should_panic(expected = "Must have a percentage for every interval")]
+    fn test_graded_invalid_intervals() {
+        schedule(None, VestingCurve::Graded(vec![dec!(50), dec!(50)]));
+    }
+
+    #[test]
+    #[should_panic(expected = "Lump sum must have a cliff")]
+    fn test_lump_sum_without_cliff() {
+        schedule(None, VestingCurve::CliffLumpSum);
+    }
+
+    #[test]
+    #[should_panic(expected = "Exponent must be greater than 1")]
+    fn test_back_loaded_invalid_exponent() {
+        schedule(None, VestingCurve::BackLoaded(1u8));
+    }
 }
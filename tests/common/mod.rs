@@ -1,3 +1,5 @@
+#![allow(dead_code)]
+use diamondpay::types::ListingFee;
 use scrypto_test::prelude::*;
 
 #[derive(Clone)]
@@ -15,6 +17,7 @@ pub struct TestSetup {
     pub package_address: PackageAddress,
     pub admin: MemberData,
     pub member: MemberData,
+    pub arbiter: MemberData,
     pub resource_address: ResourceAddress,
     pub marketplace_address: ComponentAddress,
 }
@@ -111,8 +114,8 @@ fn create_marketplace(
                     "Design",
                     "Growth"
                 ),
-                dec!(100), // minimum
-                dec!(100), // fee
+                dec!(100),                                           // minimum
+                HashMap::from([(XRD, ListingFee::Flat(dec!(100)))]), // fee
                 resource_address
             ),
         )
@@ -133,6 +136,7 @@ pub fn setup_test() -> (
     let (mut test_runner, package_address) = create_env();
     let admin = create_member(&mut test_runner, "handle_1", package_address);
     let member = create_member(&mut test_runner, "handle_2", package_address);
+    let arbiter = create_member(&mut test_runner, "handle_3", package_address);
     let resource_address = test_runner.create_freely_mintable_and_burnable_fungible_resource(
         OwnerRole::None,
         Some(dec!(11000)),
@@ -150,8 +154,63 @@ pub fn setup_test() -> (
         package_address,
         admin,
         member,
+        arbiter,
         resource_address,
         marketplace_address,
     };
     (test_runner, app_setup)
 }
+
+pub fn component_test(
+    test_runner: &mut LedgerSimulator<NoExtension, InMemorySubstateDatabase>,
+    member: MemberData,
+    component_address: ComponentAddress,
+    method_name: &str,
+    args: impl ResolvableArguments,
+) {
+    component_receipt(test_runner, member, component_address, method_name, args)
+        .expect_commit_success();
+}
+
+// returns the receipt so callers can check a rejected call
+pub fn component_receipt(
+    test_runner: &mut LedgerSimulator<NoExtension, InMemorySubstateDatabase>,
+    member: MemberData,
+    component_address: ComponentAddress,
+    method_name: &str,
+    args: impl ResolvableArguments,
+) -> TransactionReceipt {
+    let public_key = member.public_key;
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .create_proof_from_account_of_non_fungibles(
+            member.account_address,
+            member.resource_address,
+            vec![member.lid.clone()],
+        )
+        .call_method(component_address, method_name, args)
+        .call_method(
+            member.account_address,
+            "deposit_batch",
+            manifest_args!(ManifestExpression::EntireWorktop),
+        )
+        .build();
+    test_runner.execute_manifest(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(&public_key)],
+    )
+}
+
+pub fn component_call<T: ScryptoDecode>(
+    test_runner: &mut LedgerSimulator<NoExtension, InMemorySubstateDatabase>,
+    component_address: ComponentAddress,
+    method_name: &str,
+    args: impl ResolvableArguments,
+) -> T {
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .call_method(component_address, method_name, args)
+        .build();
+    let receipt = test_runner.execute_manifest(manifest, vec![]);
+    receipt.expect_commit_success().output(1)
+}
@@ -1,7 +1,28 @@
 use diamondpay::job::job_test::JobState;
+use diamondpay::types::{
+    Balances, ContractKind, DepositEvent, ListingFee, TxData, TxType, YearData,
+};
+use diamondpay::vesting_schedule::{Severance, VestingCurve, VestingUnit};
 use scrypto_test::prelude::*;
 mod common;
 
+// amount, vested, withdrawn, reserved, end epoch, is signed
+type MemberSchedule = (Decimal, Decimal, Decimal, Decimal, i64, bool);
+// marketplaces, category, admin badge, unallocated, resource, is joinable, is cancelled,
+// address, notice period, cancel request epoch
+type JobData = (
+    HashSet<ComponentAddress>,
+    String,
+    ResourceAddress,
+    Decimal,
+    ResourceAddress,
+    bool,
+    bool,
+    ComponentAddress,
+    i64,
+    Option<Decimal>,
+);
+
 fn create_job(
     test_runner: &mut LedgerSimulator<NoExtension, InMemorySubstateDatabase>,
     package_address: PackageAddress,
@@ -11,6 +32,8 @@ fn create_job(
     cliff: Option<i64>,
     end: i64,
     interval: i64,
+    notice_period: i64,
+    severance: Option<Severance>,
 ) -> ComponentAddress {
     let public_key = admin.public_key;
     let manifest = ManifestBuilder::new()
@@ -35,7 +58,11 @@ fn create_job(
                 cliff,
                 end,
                 interval,
+                VestingUnit::Days,
+                VestingCurve::Linear,
                 false,
+                notice_period,
+                severance,
                 "https://google.com",
                 "Blueprint",
                 HashMap::from([
@@ -63,13 +90,81 @@ fn create_job(
     components[0]
 }
 
-fn job_test(
+fn create_multi_job(
+    test_runner: &mut LedgerSimulator<NoExtension, InMemorySubstateDatabase>,
+    package_address: PackageAddress,
+    admin: common::MemberData,
+    resource_address: ResourceAddress,
+    severance: Option<Severance>,
+) -> ComponentAddress {
+    let public_key = admin.public_key;
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .create_proof_from_account_of_non_fungibles(
+            admin.account_address,
+            admin.resource_address,
+            vec![admin.lid.clone()],
+        )
+        .pop_from_auth_zone("proof")
+        .call_function_with_name_lookup(package_address, "Job", "instantiate_multi", |lookup| {
+            (
+                admin.account_address,
+                Some(admin.member_component),
+                admin.resource_address,
+                lookup.proof("proof"),
+                "team_handle",
+                "contract_handle",
+                "Contract Name",
+                resource_address,
+                false,
+                1i64, // notice period
+                severance,
+                "https://google.com",
+                "Blueprint",
+                HashMap::from([("description", "Test description goes here")]),
+            )
+        })
+        .call_method(
+            admin.account_address,
+            "deposit_batch",
+            manifest_args!(ManifestExpression::EntireWorktop),
+        )
+        .build();
+
+    let receipt = test_runner.execute_manifest(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(&public_key)],
+    );
+    let outcome = receipt.expect_commit_success();
+    let components = outcome.new_component_addresses();
+    components[0]
+}
+
+// requests the cancellation, then finalizes it once the clock is past the notice period
+fn job_cancellation(
     test_runner: &mut LedgerSimulator<NoExtension, InMemorySubstateDatabase>,
     member: common::MemberData,
     job_address: ComponentAddress,
-    method_name: &str,
-    args: impl ResolvableArguments,
+    round: u64,
+    finalize_ms: i64,
 ) {
+    common::component_test(
+        test_runner,
+        member.clone(),
+        job_address,
+        "request_cancellation",
+        manifest_args!(),
+    );
+    test_runner.advance_to_round_at_timestamp(Round::of(round), finalize_ms);
+    job_finalize(test_runner, member, job_address).expect_commit_success();
+}
+
+// requests & finalizes in the same tx, which the notice period must reject
+fn job_request_finalize(
+    test_runner: &mut LedgerSimulator<NoExtension, InMemorySubstateDatabase>,
+    member: common::MemberData,
+    job_address: ComponentAddress,
+) -> TransactionReceipt {
     let public_key = member.public_key;
     let manifest = ManifestBuilder::new()
         .lock_fee_from_faucet()
@@ -78,24 +173,51 @@ fn job_test(
             member.resource_address,
             vec![member.lid.clone()],
         )
-        .call_method(job_address, method_name, args)
+        .call_method(job_address, "request_cancellation", manifest_args!())
+        .call_method(job_address, "finalize_cancellation", manifest_args!())
         .call_method(
             member.account_address,
             "deposit_batch",
             manifest_args!(ManifestExpression::EntireWorktop),
         )
         .build();
-    let receipt = test_runner.execute_manifest(
+    test_runner.execute_manifest(
         manifest,
         vec![NonFungibleGlobalId::from_public_key(&public_key)],
-    );
-    receipt.expect_commit_success();
+    )
 }
 
-fn job_cancellation(
+// returns the receipt so callers can check a finalize before the notice period ends
+fn job_finalize(
+    test_runner: &mut LedgerSimulator<NoExtension, InMemorySubstateDatabase>,
+    member: common::MemberData,
+    job_address: ComponentAddress,
+) -> TransactionReceipt {
+    let public_key = member.public_key;
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .create_proof_from_account_of_non_fungibles(
+            member.account_address,
+            member.resource_address,
+            vec![member.lid.clone()],
+        )
+        .call_method(job_address, "finalize_cancellation", manifest_args!())
+        .call_method(
+            member.account_address,
+            "deposit_batch",
+            manifest_args!(ManifestExpression::EntireWorktop),
+        )
+        .build();
+    test_runner.execute_manifest(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(&public_key)],
+    )
+}
+
+fn job_join(
     test_runner: &mut LedgerSimulator<NoExtension, InMemorySubstateDatabase>,
     member: common::MemberData,
-    component_address: ComponentAddress,
+    job_address: ComponentAddress,
 ) {
     let public_key = member.public_key;
     let manifest = ManifestBuilder::new()
@@ -105,7 +227,10 @@ fn job_cancellation(
             member.resource_address,
             vec![member.lid.clone()],
         )
-        .call_method(component_address, "cancellation", manifest_args!())
+        .pop_from_auth_zone("proof")
+        .call_method_with_name_lookup(job_address, "join", |lookup| {
+            (member.resource_address, lookup.proof("proof"))
+        })
         .call_method(
             member.account_address,
             "deposit_batch",
@@ -119,12 +244,13 @@ fn job_cancellation(
     receipt.expect_commit_success();
 }
 
-fn job_join(
+fn member_join(
     test_runner: &mut LedgerSimulator<NoExtension, InMemorySubstateDatabase>,
     member: common::MemberData,
     job_address: ComponentAddress,
 ) {
     let public_key = member.public_key;
+    // one proof authorizes the member component, the other is forwarded to the job
     let manifest = ManifestBuilder::new()
         .lock_fee_from_faucet()
         .create_proof_from_account_of_non_fungibles(
@@ -132,9 +258,14 @@ fn job_join(
             member.resource_address,
             vec![member.lid.clone()],
         )
+        .create_proof_from_account_of_non_fungibles(
+            member.account_address,
+            member.resource_address,
+            vec![member.lid.clone()],
+        )
         .pop_from_auth_zone("proof")
-        .call_method_with_name_lookup(job_address, "join", |lookup| {
-            (member.resource_address, lookup.proof("proof"))
+        .call_method_with_name_lookup(member.member_component, "join_job", |lookup| {
+            (job_address, lookup.proof("proof"))
         })
         .call_method(
             member.account_address,
@@ -149,6 +280,35 @@ fn job_join(
     receipt.expect_commit_success();
 }
 
+fn job_apply(
+    test_runner: &mut LedgerSimulator<NoExtension, InMemorySubstateDatabase>,
+    member: common::MemberData,
+    job_address: ComponentAddress,
+) {
+    let public_key = member.public_key;
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .create_proof_from_account_of_non_fungibles(
+            member.account_address,
+            member.resource_address,
+            vec![member.lid.clone()],
+        )
+        .pop_from_auth_zone("proof")
+        .call_method_with_name_lookup(job_address, "apply", |lookup| {
+            (
+                member.resource_address,
+                lookup.proof("proof"),
+                HashMap::from([("message", "Interested in this job")]),
+            )
+        })
+        .build();
+    let receipt = test_runner.execute_manifest(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(&public_key)],
+    );
+    receipt.expect_commit_success();
+}
+
 fn job_leave(
     test_runner: &mut LedgerSimulator<NoExtension, InMemorySubstateDatabase>,
     member: common::MemberData,
@@ -231,7 +391,16 @@ fn job_deposit(
         manifest,
         vec![NonFungibleGlobalId::from_public_key(&public_key)],
     );
-    receipt.expect_commit_success();
+    let outcome = receipt.expect_commit_success();
+    // every deposit is also emitted as a typed event
+    let (_, data) = outcome
+        .application_events
+        .iter()
+        .find(|(id, _)| test_runner.event_name(id) == "DepositEvent")
+        .unwrap();
+    let event = scrypto_decode::<DepositEvent>(data).unwrap();
+    assert_eq!(event.contract_address, job_address);
+    assert_eq!(event.amount, amount);
 }
 
 fn job_list(
@@ -257,7 +426,12 @@ fn job_list(
         .call_method(job_address, "list", manifest_args!(marketplace_address))
         .pop_from_auth_zone("proof")
         .call_method_with_name_lookup(marketplace_address, "add_job", |lookup| {
-            (job_address, lookup.proof("proof"), lookup.bucket("bucket1"))
+            (
+                job_address,
+                lookup.proof("proof"),
+                lookup.bucket("bucket1"),
+                None::<ResourceAddress>,
+            )
         })
         .call_method(
             member.account_address,
@@ -272,6 +446,32 @@ fn job_list(
     receipt.expect_commit_success();
 }
 
+fn job_delist(
+    test_runner: &mut LedgerSimulator<NoExtension, InMemorySubstateDatabase>,
+    member: common::MemberData,
+    job_address: ComponentAddress,
+    marketplace_address: ComponentAddress,
+) {
+    let public_key = member.public_key;
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .create_proof_from_account_of_non_fungibles(
+            member.account_address,
+            member.resource_address,
+            vec![member.lid.clone()],
+        )
+        .pop_from_auth_zone("proof")
+        .call_method_with_name_lookup(marketplace_address, "delist_job", |lookup| {
+            (job_address, Some(lookup.proof("proof")))
+        })
+        .build();
+    let receipt = test_runner.execute_manifest(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(&public_key)],
+    );
+    receipt.expect_commit_success();
+}
+
 #[test]
 fn test_members() {
     let (mut test_runner, app) = common::setup_test();
@@ -285,31 +485,46 @@ fn test_members() {
         1725859156i64,
         // 1792176036i64,
         14i64,
+        1i64,
+        None,
     );
-    println!("Job Address: {:?}", job_address);
 
-    job_test(
+    common::component_test(
         &mut test_runner,
         app.admin.clone(),
         job_address,
         "invite",
         manifest_args!(app.member.resource_address, "handle_2"),
     );
-    job_test(
+    common::component_test(
         &mut test_runner,
         app.admin.clone(),
         job_address,
         "remove",
         manifest_args!(app.member.resource_address),
     );
-    job_test(
+    // removing the single member makes the Job joinable again
+    let (_, _, _, _, _, is_joinable, _, _, _, _): JobData =
+        common::component_call(&mut test_runner, job_address, "data", manifest_args!());
+    assert!(is_joinable);
+    common::component_test(
         &mut test_runner,
         app.admin.clone(),
         job_address,
         "invite",
         manifest_args!(app.member.resource_address, "handle_2"),
     );
-    job_test(
+    let txs: Vec<TxData> = common::component_call(
+        &mut test_runner,
+        job_address,
+        "txs_by_type",
+        manifest_args!(TxType::Invite, 1u64, 20u64),
+    );
+    assert!(txs.len() == 2);
+    assert!(txs
+        .iter()
+        .all(|tx| tx.tx_type == TxType::Invite && tx.to_badge == app.member.resource_address));
+    common::component_test(
         &mut test_runner,
         app.member.clone(),
         app.member.member_component,
@@ -325,8 +540,74 @@ fn test_members() {
         dec!(10000),
         job_address,
     );
+    // a single Job reports its deposited amount
+    let (_, _, _, amount, _, _, _, _, _, _): JobData =
+        common::component_call(&mut test_runner, job_address, "data", manifest_args!());
+    assert!(amount == dec!(10000));
+    let (_, vested, _, _, end_epoch, _): MemberSchedule = common::component_call(
+        &mut test_runner,
+        job_address,
+        "member_data",
+        manifest_args!(app.member.resource_address),
+    );
+    common::component_test(
+        &mut test_runner,
+        app.admin.clone(),
+        job_address,
+        "pause",
+        manifest_args!(app.member.resource_address),
+    );
+
+    // Nothing vests while paused
+    test_runner.advance_to_round_at_timestamp(Round::of(1), 1695236716000i64 + 28 * 86400000);
+    let (_, paused_vested, _, _, _, _): MemberSchedule = common::component_call(
+        &mut test_runner,
+        job_address,
+        "member_data",
+        manifest_args!(app.member.resource_address),
+    );
+    assert!(paused_vested == vested);
+
+    // Resuming excludes the paused time & pushes back the end date
+    common::component_test(
+        &mut test_runner,
+        app.admin.clone(),
+        job_address,
+        "resume",
+        manifest_args!(app.member.resource_address),
+    );
+    let (_, resumed_vested, _, _, resumed_end_epoch, _): MemberSchedule = common::component_call(
+        &mut test_runner,
+        job_address,
+        "member_data",
+        manifest_args!(app.member.resource_address),
+    );
+    assert!(resumed_vested == vested);
+    assert!(resumed_end_epoch == end_epoch + 28 * 86400);
     job_withdraw(&mut test_runner, app.member.clone(), job_address);
     job_leave(&mut test_runner, app.member.clone(), job_address);
+
+    let year_data: Option<YearData> = common::component_call(
+        &mut test_runner,
+        job_address,
+        "tx_year",
+        manifest_args!(dec!(2023)),
+    );
+    let year_data = year_data.unwrap();
+    let resource = app.resource_address;
+    assert!(year_data.deposited == HashMap::from([(resource, dec!(10000))]));
+    assert!(year_data.rewarded.is_empty());
+    let j_state: JobState = test_runner.component_state(job_address);
+    let withdrawn = j_state.vesting_schedule.withdrawn;
+    assert!(withdrawn > dec!(0));
+    assert!(year_data.withdrawn == HashMap::from([(resource, withdrawn)]));
+    assert!(year_data.cancelled.is_empty());
+
+    // the member left, so the admin finalizes without a notice period, only once
+    job_finalize(&mut test_runner, app.admin.clone(), job_address).expect_commit_success();
+    let j_state: JobState = test_runner.component_state(job_address);
+    assert!(j_state.is_cancelled);
+    job_finalize(&mut test_runner, app.admin.clone(), job_address).expect_commit_failure();
 }
 
 #[test]
@@ -336,14 +617,20 @@ fn test() {
 
     let (mut test_runner, app) = common::setup_test();
     // Marketplace add market
-    job_test(
+    common::component_test(
         &mut test_runner,
         app.admin.clone(),
         app.marketplace_address,
         "add_category",
-        manifest_args!(vec!["Main"], dec!(2000), dec!(100), app.resource_address),
+        manifest_args!(
+            vec!["Main"],
+            Some(ContractKind::Job),
+            dec!(2000),
+            HashMap::from([(XRD, ListingFee::Flat(dec!(100)))]),
+            app.resource_address
+        ),
     );
-    job_test(
+    common::component_test(
         &mut test_runner,
         app.admin.clone(),
         app.marketplace_address,
@@ -352,6 +639,9 @@ fn test() {
             "Main",
             false,
             dec!(3000),
+            HashMap::from([(XRD, ListingFee::Flat(dec!(50)))]),
+            app.resource_address,
+            true,
             HashMap::from([("description", "A description")])
         ),
     );
@@ -366,8 +656,9 @@ fn test() {
         1725859156i64,
         // 1792176036i64,
         14i64,
+        1i64,
+        None,
     );
-    println!("Job Address: {:?}", job_address);
 
     job_deposit(
         &mut test_runner,
@@ -377,7 +668,7 @@ fn test() {
         job_address,
     );
 
-    job_test(
+    common::component_test(
         &mut test_runner,
         app.admin.clone(),
         job_address,
@@ -394,23 +685,25 @@ fn test() {
             job_address,
             app.marketplace_address,
         );
-        job_test(
+        common::component_test(
             &mut test_runner,
             app.admin.clone(),
             app.marketplace_address,
             "withdraw",
             manifest_args!(XRD),
         );
-        job_test(
+        common::component_test(
             &mut test_runner,
             app.admin.clone(),
             app.marketplace_address,
             "remove_contract",
             manifest_args!(job_address, "Blueprint", false),
         );
+        let j_state: JobState = test_runner.component_state(job_address);
+        assert!(j_state.marketplaces.is_empty());
     }
 
-    job_test(
+    common::component_test(
         &mut test_runner,
         app.admin.clone(),
         job_address,
@@ -418,7 +711,7 @@ fn test() {
         manifest_args!(app.member.resource_address, app.member.handle.clone()),
     );
     job_join(&mut test_runner, app.member.clone(), job_address);
-    job_test(
+    common::component_test(
         &mut test_runner,
         app.member.clone(),
         app.member.member_component,
@@ -429,10 +722,741 @@ fn test() {
     if !is_list {
         job_withdraw(&mut test_runner, app.member.clone(), job_address);
         job_leave(&mut test_runner, app.member.clone(), job_address);
-        job_cancellation(&mut test_runner, app.admin.clone(), job_address);
+        job_cancellation(
+            &mut test_runner,
+            app.admin.clone(),
+            job_address,
+            1,
+            1695236716000i64 + 1000,
+        );
 
         let j_state: JobState = test_runner.component_state(job_address);
-        println!("State: {:?}", j_state.vesting_schedule.withdrawn);
         assert!(j_state.vesting_schedule.withdrawn == dec!(4979.477));
     }
 }
+
+#[test]
+fn test_delist() {
+    let (mut test_runner, app) = common::setup_test();
+    let job_address = create_job(
+        &mut test_runner,
+        app.package_address,
+        app.admin.clone(),
+        app.resource_address,
+        1662700716i64,
+        Some(1694236716i64),
+        1725859156i64,
+        14i64,
+        1i64,
+        None,
+    );
+    job_deposit(
+        &mut test_runner,
+        app.admin.clone(),
+        app.resource_address,
+        dec!(10000),
+        job_address,
+    );
+    job_list(
+        &mut test_runner,
+        app.admin.clone(),
+        job_address,
+        app.marketplace_address,
+    );
+
+    // the contract admin delists their own Job
+    job_delist(
+        &mut test_runner,
+        app.admin.clone(),
+        job_address,
+        app.marketplace_address,
+    );
+    let j_state: JobState = test_runner.component_state(job_address);
+    assert!(j_state.marketplaces.is_empty());
+}
+
+#[test]
+fn test_multi() {
+    let (mut test_runner, app) = common::setup_test();
+    let job_address = create_multi_job(
+        &mut test_runner,
+        app.package_address,
+        app.admin.clone(),
+        app.resource_address,
+        Some(Severance::Amount(dec!(500))),
+    );
+
+    job_deposit(
+        &mut test_runner,
+        app.admin.clone(),
+        app.resource_address,
+        dec!(10000),
+        job_address,
+    );
+    // Fully vested schedule
+    common::component_test(
+        &mut test_runner,
+        app.admin.clone(),
+        job_address,
+        "invite_member",
+        manifest_args!(
+            app.member.resource_address,
+            "handle_2",
+            1662700716i64,
+            None::<i64>,
+            1694236716i64,
+            14i64,
+            VestingUnit::Days,
+            VestingCurve::Linear,
+            dec!(4000)
+        ),
+    );
+    // Schedule that hasn't started vesting
+    common::component_test(
+        &mut test_runner,
+        app.admin.clone(),
+        job_address,
+        "invite_member",
+        manifest_args!(
+            app.arbiter.resource_address,
+            "handle_3",
+            1725859156i64,
+            None::<i64>,
+            1733721556i64,
+            1i64,
+            VestingUnit::Months,
+            VestingCurve::Graded(vec![dec!(25), dec!(25), dec!(50)]),
+            dec!(6000)
+        ),
+    );
+    job_join(&mut test_runner, app.member.clone(), job_address);
+    job_join(&mut test_runner, app.arbiter.clone(), job_address);
+    job_withdraw(&mut test_runner, app.member.clone(), job_address);
+    job_cancellation(
+        &mut test_runner,
+        app.admin.clone(),
+        job_address,
+        1,
+        1695236716000i64 + 1000,
+    );
+    // the unvested member is paid the severance
+    job_withdraw(&mut test_runner, app.arbiter.clone(), job_address);
+
+    let j_state: JobState = test_runner.component_state(job_address);
+    let member_schedule = j_state.schedules.get(&app.member.resource_address).unwrap();
+    assert!(member_schedule.withdrawn == dec!(4000));
+    let arbiter_schedule = j_state
+        .schedules
+        .get(&app.arbiter.resource_address)
+        .unwrap();
+    assert!(arbiter_schedule.withdrawn == dec!(500));
+    assert!(j_state.is_cancelled);
+
+    // the withdrawn severance counts as owed, leaving nothing unallocated
+    let (_, vested, withdrawn, reserved, _, _): MemberSchedule = common::component_call(
+        &mut test_runner,
+        job_address,
+        "member_data",
+        manifest_args!(app.arbiter.resource_address),
+    );
+    assert!(vested == dec!(500));
+    assert!(withdrawn == dec!(500));
+    assert!(reserved == dec!(0));
+    let (_, _, _, unallocated, _, _, is_cancelled, _, _, _): JobData =
+        common::component_call(&mut test_runner, job_address, "data", manifest_args!());
+    assert!(unallocated == dec!(0));
+    assert!(is_cancelled);
+}
+
+#[test]
+fn test_applications() {
+    let (mut test_runner, app) = common::setup_test();
+    let job_address = create_multi_job(
+        &mut test_runner,
+        app.package_address,
+        app.admin.clone(),
+        app.resource_address,
+        Some(Severance::Amount(dec!(500))),
+    );
+    job_deposit(
+        &mut test_runner,
+        app.admin.clone(),
+        app.resource_address,
+        dec!(10000),
+        job_address,
+    );
+
+    // The admin can clear the queue
+    job_apply(&mut test_runner, app.member.clone(), job_address);
+    job_apply(&mut test_runner, app.arbiter.clone(), job_address);
+    common::component_test(
+        &mut test_runner,
+        app.admin.clone(),
+        job_address,
+        "clear_applications",
+        manifest_args!(),
+    );
+    let j_state: JobState = test_runner.component_state(job_address);
+    assert!(j_state.applications.is_empty());
+
+    // Accepted with the member's schedule in multi mode
+    job_apply(&mut test_runner, app.member.clone(), job_address);
+    common::component_test(
+        &mut test_runner,
+        app.admin.clone(),
+        job_address,
+        "accept_member_application",
+        manifest_args!(
+            app.member.resource_address,
+            1662700716i64,
+            None::<i64>,
+            1694236716i64,
+            14i64,
+            VestingUnit::Days,
+            VestingCurve::Linear,
+            dec!(4000)
+        ),
+    );
+    job_join(&mut test_runner, app.member.clone(), job_address);
+    job_withdraw(&mut test_runner, app.member.clone(), job_address);
+
+    let j_state: JobState = test_runner.component_state(job_address);
+    assert!(j_state.applications.is_empty());
+    let member_schedule = j_state.schedules.get(&app.member.resource_address).unwrap();
+    assert!(member_schedule.withdrawn == dec!(4000));
+
+    job_apply(&mut test_runner, app.arbiter.clone(), job_address);
+    common::component_test(
+        &mut test_runner,
+        app.admin.clone(),
+        job_address,
+        "request_cancellation",
+        manifest_args!(),
+    );
+    // applications can't be accepted once a cancellation is requested
+    common::component_receipt(
+        &mut test_runner,
+        app.admin.clone(),
+        job_address,
+        "accept_member_application",
+        manifest_args!(
+            app.arbiter.resource_address,
+            1662700716i64,
+            None::<i64>,
+            1694236716i64,
+            14i64,
+            VestingUnit::Days,
+            VestingCurve::Linear,
+            dec!(1000)
+        ),
+    )
+    .expect_commit_failure();
+}
+
+#[test]
+fn test_member_custody() {
+    let (mut test_runner, app) = common::setup_test();
+    let job_address = create_multi_job(
+        &mut test_runner,
+        app.package_address,
+        app.admin.clone(),
+        app.resource_address,
+        Some(Severance::Amount(dec!(500))),
+    );
+    job_deposit(
+        &mut test_runner,
+        app.admin.clone(),
+        app.resource_address,
+        dec!(10000),
+        job_address,
+    );
+    common::component_test(
+        &mut test_runner,
+        app.admin.clone(),
+        job_address,
+        "invite_member",
+        manifest_args!(
+            app.member.resource_address,
+            "handle_2",
+            1662700716i64,
+            None::<i64>,
+            1694236716i64,
+            14i64,
+            VestingUnit::Days,
+            VestingCurve::Linear,
+            dec!(4000)
+        ),
+    );
+    common::component_test(
+        &mut test_runner,
+        app.member.clone(),
+        app.member.member_component,
+        "set_custody",
+        manifest_args!(true),
+    );
+    member_join(&mut test_runner, app.member.clone(), job_address);
+    let j_state: JobState = test_runner.component_state(job_address);
+    assert!(j_state.signatures.contains(&app.member.resource_address));
+
+    // the custodied job badge proves the withdrawal
+    common::component_test(
+        &mut test_runner,
+        app.member.clone(),
+        app.member.member_component,
+        "withdraw_from_job",
+        manifest_args!(job_address),
+    );
+
+    let j_state: JobState = test_runner.component_state(job_address);
+    let member_schedule = j_state.schedules.get(&app.member.resource_address).unwrap();
+    assert!(member_schedule.withdrawn == dec!(4000));
+    let balances: Balances = common::component_call(
+        &mut test_runner,
+        app.member.member_component,
+        "balances",
+        manifest_args!(),
+    );
+    assert!(balances.fungibles == HashMap::from([(app.resource_address, dec!(4000))]));
+    let badge_ids: Vec<&IndexSet<NonFungibleLocalId>> = balances.non_fungibles.values().collect();
+    assert!(badge_ids == vec![&indexset!(NonFungibleLocalId::string("handle_2").unwrap())]);
+}
+
+#[test]
+fn test_member_deposit_badge() {
+    let (mut test_runner, app) = common::setup_test();
+    let job_address = create_multi_job(
+        &mut test_runner,
+        app.package_address,
+        app.admin.clone(),
+        app.resource_address,
+        None,
+    );
+    job_deposit(
+        &mut test_runner,
+        app.admin.clone(),
+        app.resource_address,
+        dec!(10000),
+        job_address,
+    );
+    for (member, handle) in [
+        (app.member.clone(), "handle_2"),
+        (app.arbiter.clone(), "handle_3"),
+    ] {
+        common::component_test(
+            &mut test_runner,
+            app.admin.clone(),
+            job_address,
+            "invite_member",
+            manifest_args!(
+                member.resource_address,
+                handle,
+                1662700716i64,
+                None::<i64>,
+                1694236716i64,
+                14i64,
+                VestingUnit::Days,
+                VestingCurve::Linear,
+                dec!(4000)
+            ),
+        );
+    }
+
+    // a badge held by the account isn't in custody
+    member_join(&mut test_runner, app.arbiter.clone(), job_address);
+    common::component_receipt(
+        &mut test_runner,
+        app.arbiter.clone(),
+        app.arbiter.member_component,
+        "withdraw_from_job",
+        manifest_args!(job_address),
+    )
+    .expect_commit_failure();
+
+    // the badge returned by the join is put in custody once custody is turned on
+    let member = app.member.clone();
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .create_proof_from_account_of_non_fungibles(
+            member.account_address,
+            member.resource_address,
+            vec![member.lid.clone()],
+        )
+        .create_proof_from_account_of_non_fungibles(
+            member.account_address,
+            member.resource_address,
+            vec![member.lid.clone()],
+        )
+        .pop_from_auth_zone("proof")
+        .call_method_with_name_lookup(member.member_component, "join_job", |lookup| {
+            (job_address, lookup.proof("proof"))
+        })
+        .call_method(member.member_component, "set_custody", manifest_args!(true))
+        .call_method(
+            member.member_component,
+            "deposit_badges",
+            manifest_args!(ManifestExpression::EntireWorktop),
+        )
+        .build();
+    test_runner
+        .execute_manifest(
+            manifest,
+            vec![NonFungibleGlobalId::from_public_key(&member.public_key)],
+        )
+        .expect_commit_success();
+    common::component_test(
+        &mut test_runner,
+        app.member.clone(),
+        app.member.member_component,
+        "withdraw_from_job",
+        manifest_args!(job_address),
+    );
+    let balances: Balances = common::component_call(
+        &mut test_runner,
+        app.member.member_component,
+        "balances",
+        manifest_args!(),
+    );
+    assert!(balances.fungibles == HashMap::from([(app.resource_address, dec!(4000))]));
+}
+
+#[test]
+fn test_notice_period() {
+    let (mut test_runner, app) = common::setup_test();
+    // 30 day notice period
+    let job_address = create_job(
+        &mut test_runner,
+        app.package_address,
+        app.admin.clone(),
+        app.resource_address,
+        1662700716i64,
+        None,
+        1725859156i64,
+        14i64,
+        2592000i64,
+        None,
+    );
+    job_deposit(
+        &mut test_runner,
+        app.admin.clone(),
+        app.resource_address,
+        dec!(10000),
+        job_address,
+    );
+    common::component_test(
+        &mut test_runner,
+        app.admin.clone(),
+        job_address,
+        "invite",
+        manifest_args!(app.member.resource_address, app.member.handle.clone()),
+    );
+    job_join(&mut test_runner, app.member.clone(), job_address);
+    job_request_finalize(&mut test_runner, app.admin.clone(), job_address).expect_commit_failure();
+    common::component_test(
+        &mut test_runner,
+        app.admin.clone(),
+        job_address,
+        "request_cancellation",
+        manifest_args!(),
+    );
+    job_finalize(&mut test_runner, app.admin.clone(), job_address).expect_commit_failure();
+    let (_, requested_vested, _, _, _, _): MemberSchedule = common::component_call(
+        &mut test_runner,
+        job_address,
+        "member_data",
+        manifest_args!(app.member.resource_address),
+    );
+
+    // vesting continues during the notice period
+    test_runner.advance_to_round_at_timestamp(Round::of(1), 1695236716000i64 + 28 * 86400000);
+    let (_, notice_vested, _, _, _, _): MemberSchedule = common::component_call(
+        &mut test_runner,
+        job_address,
+        "member_data",
+        manifest_args!(app.member.resource_address),
+    );
+    assert!(notice_vested > requested_vested);
+    job_finalize(&mut test_runner, app.admin.clone(), job_address).expect_commit_failure();
+
+    test_runner.advance_to_round_at_timestamp(Round::of(2), 1695236716000i64 + 30 * 86400000);
+    job_finalize(&mut test_runner, app.admin.clone(), job_address).expect_commit_success();
+    let (_, cancelled_vested, _, _, _, _): MemberSchedule = common::component_call(
+        &mut test_runner,
+        job_address,
+        "member_data",
+        manifest_args!(app.member.resource_address),
+    );
+    assert!(cancelled_vested >= notice_vested);
+
+    // vesting stops once finalized
+    test_runner.advance_to_round_at_timestamp(Round::of(3), 1695236716000i64 + 60 * 86400000);
+    let (_, final_vested, _, _, _, _): MemberSchedule = common::component_call(
+        &mut test_runner,
+        job_address,
+        "member_data",
+        manifest_args!(app.member.resource_address),
+    );
+    assert!(final_vested == cancelled_vested);
+    let j_state: JobState = test_runner.component_state(job_address);
+    assert!(j_state.is_cancelled);
+    // a finalized cancellation can't run again
+    job_finalize(&mut test_runner, app.admin.clone(), job_address).expect_commit_failure();
+}
+
+#[test]
+fn test_severance_intervals() {
+    let (mut test_runner, app) = common::setup_test();
+    let job_address = create_multi_job(
+        &mut test_runner,
+        app.package_address,
+        app.admin.clone(),
+        app.resource_address,
+        Some(Severance::Intervals(2)),
+    );
+    job_deposit(
+        &mut test_runner,
+        app.admin.clone(),
+        app.resource_address,
+        dec!(10000),
+        job_address,
+    );
+    // started 20 days ago, 10 intervals of 14 days vesting 604.8 each
+    common::component_test(
+        &mut test_runner,
+        app.admin.clone(),
+        job_address,
+        "invite_member",
+        manifest_args!(
+            app.member.resource_address,
+            "handle_2",
+            1693508716i64,
+            None::<i64>,
+            1705604716i64,
+            14i64,
+            VestingUnit::Days,
+            VestingCurve::Linear,
+            dec!(6048)
+        ),
+    );
+    // an unsigned member's vested amount is swept to the admin on cancellation
+    common::component_test(
+        &mut test_runner,
+        app.admin.clone(),
+        job_address,
+        "invite_member",
+        manifest_args!(
+            app.arbiter.resource_address,
+            "handle_3",
+            1662700716i64,
+            None::<i64>,
+            1694236716i64,
+            14i64,
+            VestingUnit::Days,
+            VestingCurve::Linear,
+            dec!(1000)
+        ),
+    );
+    job_join(&mut test_runner, app.member.clone(), job_address);
+    job_cancellation(
+        &mut test_runner,
+        app.admin.clone(),
+        job_address,
+        1,
+        1695236716000i64 + 1000,
+    );
+
+    // the vested interval plus 2 intervals of severance
+    let (_, vested, _, reserved, _, _): MemberSchedule = common::component_call(
+        &mut test_runner,
+        job_address,
+        "member_data",
+        manifest_args!(app.member.resource_address),
+    );
+    assert!(vested == dec!("1814.4"));
+    assert!(reserved == dec!("1814.4"));
+    let balance =
+        test_runner.get_component_balance(app.admin.account_address, app.resource_address);
+    assert!(balance == dec!("9185.6"));
+    job_withdraw(&mut test_runner, app.member.clone(), job_address);
+    let balance =
+        test_runner.get_component_balance(app.member.account_address, app.resource_address);
+    assert!(balance == dec!("1814.4"));
+    let (_, _, _, unallocated, _, _, _, _, _, _): JobData =
+        common::component_call(&mut test_runner, job_address, "data", manifest_args!());
+    assert!(unallocated == dec!(0));
+}
+
+#[test]
+fn test_severance_single() {
+    let (mut test_runner, app) = common::setup_test();
+    let job_address = create_job(
+        &mut test_runner,
+        app.package_address,
+        app.admin.clone(),
+        app.resource_address,
+        1693508716i64,
+        None,
+        1705604716i64,
+        14i64,
+        1i64,
+        Some(Severance::Amount(dec!(500))),
+    );
+    job_deposit(
+        &mut test_runner,
+        app.admin.clone(),
+        app.resource_address,
+        dec!(6048),
+        job_address,
+    );
+    common::component_test(
+        &mut test_runner,
+        app.admin.clone(),
+        job_address,
+        "invite",
+        manifest_args!(app.member.resource_address, app.member.handle.clone()),
+    );
+    job_join(&mut test_runner, app.member.clone(), job_address);
+    job_cancellation(
+        &mut test_runner,
+        app.admin.clone(),
+        job_address,
+        1,
+        1695236716000i64 + 1000,
+    );
+
+    let (_, vested, _, reserved, _, _): MemberSchedule = common::component_call(
+        &mut test_runner,
+        job_address,
+        "member_data",
+        manifest_args!(app.member.resource_address),
+    );
+    assert!(vested == dec!("1104.8"));
+    assert!(reserved == dec!("1104.8"));
+    let balance =
+        test_runner.get_component_balance(app.admin.account_address, app.resource_address);
+    assert!(balance == dec!("9895.2"));
+    job_withdraw(&mut test_runner, app.member.clone(), job_address);
+    let balance =
+        test_runner.get_component_balance(app.member.account_address, app.resource_address);
+    assert!(balance == dec!("1104.8"));
+}
+
+#[test]
+fn test_leave_severance() {
+    let (mut test_runner, app) = common::setup_test();
+    let job_address = create_multi_job(
+        &mut test_runner,
+        app.package_address,
+        app.admin.clone(),
+        app.resource_address,
+        Some(Severance::Amount(dec!(500))),
+    );
+    job_deposit(
+        &mut test_runner,
+        app.admin.clone(),
+        app.resource_address,
+        dec!(10000),
+        job_address,
+    );
+    common::component_test(
+        &mut test_runner,
+        app.admin.clone(),
+        job_address,
+        "invite_member",
+        manifest_args!(
+            app.member.resource_address,
+            "handle_2",
+            1693508716i64,
+            None::<i64>,
+            1705604716i64,
+            14i64,
+            VestingUnit::Days,
+            VestingCurve::Linear,
+            dec!(6048)
+        ),
+    );
+    job_join(&mut test_runner, app.member.clone(), job_address);
+    job_withdraw(&mut test_runner, app.member.clone(), job_address);
+    job_leave(&mut test_runner, app.member.clone(), job_address);
+    job_cancellation(
+        &mut test_runner,
+        app.admin.clone(),
+        job_address,
+        1,
+        1695236716000i64 + 1000,
+    );
+
+    // members that left aren't paid severance
+    let (_, vested, withdrawn, reserved, _, _): MemberSchedule = common::component_call(
+        &mut test_runner,
+        job_address,
+        "member_data",
+        manifest_args!(app.member.resource_address),
+    );
+    assert!(vested == dec!("604.8"));
+    assert!(withdrawn == dec!("604.8"));
+    assert!(reserved == dec!(0));
+    let balance =
+        test_runner.get_component_balance(app.admin.account_address, app.resource_address);
+    assert!(balance == dec!("10395.2"));
+}
+
+#[test]
+fn test_leave_vested() {
+    let (mut test_runner, app) = common::setup_test();
+    let job_address = create_multi_job(
+        &mut test_runner,
+        app.package_address,
+        app.admin.clone(),
+        app.resource_address,
+        None,
+    );
+    job_deposit(
+        &mut test_runner,
+        app.admin.clone(),
+        app.resource_address,
+        dec!(10000),
+        job_address,
+    );
+    common::component_test(
+        &mut test_runner,
+        app.admin.clone(),
+        job_address,
+        "invite_member",
+        manifest_args!(
+            app.member.resource_address,
+            "handle_2",
+            1693508716i64,
+            None::<i64>,
+            1705604716i64,
+            14i64,
+            VestingUnit::Days,
+            VestingCurve::Linear,
+            dec!(6048)
+        ),
+    );
+    job_join(&mut test_runner, app.member.clone(), job_address);
+    // the vested amount stays reserved for the member after leaving
+    job_leave(&mut test_runner, app.member.clone(), job_address);
+    let (_, vested, withdrawn, reserved, _, _): MemberSchedule = common::component_call(
+        &mut test_runner,
+        job_address,
+        "member_data",
+        manifest_args!(app.member.resource_address),
+    );
+    assert!(vested == dec!("604.8"));
+    assert!(withdrawn == dec!(0));
+    assert!(reserved == dec!("604.8"));
+    let (_, _, _, unallocated, _, _, _, _, _, _): JobData =
+        common::component_call(&mut test_runner, job_address, "data", manifest_args!());
+    assert!(unallocated == dec!("9395.2"));
+
+    job_withdraw(&mut test_runner, app.member.clone(), job_address);
+    let (_, _, withdrawn, reserved, _, _): MemberSchedule = common::component_call(
+        &mut test_runner,
+        job_address,
+        "member_data",
+        manifest_args!(app.member.resource_address),
+    );
+    assert!(withdrawn == dec!("604.8"));
+    assert!(reserved == dec!(0));
+}
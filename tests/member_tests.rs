@@ -1,29 +1,6 @@
 use scrypto_test::prelude::*;
 mod common;
 
-fn member_test(
-    test_runner: &mut LedgerSimulator<NoExtension, InMemorySubstateDatabase>,
-    member: common::MemberData,
-    method_name: &str,
-    args: impl ResolvableArguments,
-) {
-    let public_key = member.public_key;
-    let manifest = ManifestBuilder::new()
-        .lock_fee_from_faucet()
-        .create_proof_from_account_of_non_fungibles(
-            member.account_address,
-            member.resource_address,
-            vec![member.lid.clone()],
-        )
-        .call_method(member.member_component, method_name, args)
-        .build();
-    let receipt = test_runner.execute_manifest(
-        manifest,
-        vec![NonFungibleGlobalId::from_public_key(&public_key)],
-    );
-    receipt.expect_commit_success();
-}
-
 fn member_deposit(
     test_runner: &mut LedgerSimulator<NoExtension, InMemorySubstateDatabase>,
     member: common::MemberData,
@@ -86,21 +63,78 @@ fn member_withdraw(
     receipt.expect_commit_success();
 }
 
+fn member_approve_recovery(
+    test_runner: &mut LedgerSimulator<NoExtension, InMemorySubstateDatabase>,
+    guardian: common::MemberData,
+    member: common::MemberData,
+    account_address: ComponentAddress,
+) {
+    let public_key = guardian.public_key;
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .create_proof_from_account_of_non_fungibles(
+            guardian.account_address,
+            guardian.resource_address,
+            vec![guardian.lid.clone()],
+        )
+        .pop_from_auth_zone("proof")
+        .call_method_with_name_lookup(member.member_component, "approve_recovery", |lookup| {
+            (
+                guardian.resource_address,
+                lookup.proof("proof"),
+                account_address,
+            )
+        })
+        .build();
+    let receipt = test_runner.execute_manifest(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(&public_key)],
+    );
+    receipt.expect_commit_success();
+}
+
+// returns the receipt so callers can check a rejected recovery
+fn member_recover(
+    test_runner: &mut LedgerSimulator<NoExtension, InMemorySubstateDatabase>,
+    guardian: common::MemberData,
+    member: common::MemberData,
+) -> TransactionReceipt {
+    let public_key = guardian.public_key;
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .create_proof_from_account_of_non_fungibles(
+            guardian.account_address,
+            guardian.resource_address,
+            vec![guardian.lid.clone()],
+        )
+        .pop_from_auth_zone("proof")
+        .call_method_with_name_lookup(member.member_component, "recover", |lookup| {
+            (guardian.resource_address, lookup.proof("proof"))
+        })
+        .build();
+    test_runner.execute_manifest(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(&public_key)],
+    )
+}
+
 #[test]
 fn test() {
     let (mut test_runner, app) = common::setup_test();
-    member_test(
+    common::component_test(
         &mut test_runner,
         app.admin.clone(),
+        app.admin.member_component,
         "details",
         manifest_args!(
             HashMap::from([("description", "New Description")]),
             "https://google.com",
         ),
     );
-    member_test(
+    common::component_test(
         &mut test_runner,
         app.admin.clone(),
+        app.admin.member_component,
         "details",
         manifest_args!(
             HashMap::from([("description", "New Description 2")]),
@@ -114,37 +148,181 @@ fn test() {
         dec!(3000),
     );
     member_withdraw(&mut test_runner, app.admin.clone(), app.resource_address);
-    member_test(
+    common::component_test(
         &mut test_runner,
         app.admin.clone(),
+        app.admin.member_component,
         "update_members",
         manifest_args!(vec!(app.member.resource_address), false),
     );
-    member_test(
+    common::component_test(
         &mut test_runner,
         app.admin.clone(),
+        app.admin.member_component,
         "update_members",
         manifest_args!(vec!(app.member.resource_address), true),
     );
-    member_test(
+    common::component_test(
         &mut test_runner,
         app.admin.clone(),
+        app.admin.member_component,
         "update_team",
         manifest_args!(
             "App Name",
             HashMap::from([("description", "Test description goes here")]),
         ),
     );
-    member_test(
+    common::component_test(
         &mut test_runner,
         app.admin.clone(),
+        app.admin.member_component,
         "update_team",
         manifest_args!("App Name", HashMap::from([("description", "Updated here")]),),
     );
-    member_test(
+    common::component_test(
         &mut test_runner,
         app.admin.clone(),
+        app.admin.member_component,
         "remove_team",
         manifest_args!("App Name",),
     );
 }
+
+#[test]
+fn test_recovery() {
+    let (mut test_runner, app) = common::setup_test();
+    let (public_key, _, new_account) = test_runner.new_allocated_account();
+    common::component_test(
+        &mut test_runner,
+        app.admin.clone(),
+        app.admin.member_component,
+        "set_guardians",
+        manifest_args!(
+            vec!(app.member.resource_address, app.arbiter.resource_address),
+            2u64,
+            0i64
+        ),
+    );
+    member_approve_recovery(
+        &mut test_runner,
+        app.member.clone(),
+        app.admin.clone(),
+        new_account,
+    );
+    member_approve_recovery(
+        &mut test_runner,
+        app.arbiter.clone(),
+        app.admin.clone(),
+        new_account,
+    );
+    member_recover(&mut test_runner, app.member.clone(), app.admin.clone()).expect_commit_success();
+
+    // the replacement goes to the approved account, never to the guardian
+    let balance = test_runner.get_component_balance(new_account, app.admin.resource_address);
+    assert!(balance == dec!(1));
+    let balance =
+        test_runner.get_component_balance(app.member.account_address, app.admin.resource_address);
+    assert!(balance == dec!(0));
+
+    // the lost badge is no longer the admin, the replacement is
+    common::component_receipt(
+        &mut test_runner,
+        app.admin.clone(),
+        app.admin.member_component,
+        "set_custody",
+        manifest_args!(true),
+    )
+    .expect_commit_failure();
+    let recovered = common::MemberData {
+        lid: NonFungibleLocalId::string("handle_1_1").unwrap(),
+        account_address: new_account,
+        public_key,
+        ..app.admin.clone()
+    };
+    common::component_test(
+        &mut test_runner,
+        recovered,
+        app.admin.member_component,
+        "set_custody",
+        manifest_args!(true),
+    );
+}
+
+#[test]
+fn test_recovery_delay() {
+    let (mut test_runner, app) = common::setup_test();
+    let (_, _, new_account) = test_runner.new_allocated_account();
+    // 7 day delay
+    common::component_test(
+        &mut test_runner,
+        app.admin.clone(),
+        app.admin.member_component,
+        "set_guardians",
+        manifest_args!(
+            vec!(app.member.resource_address, app.arbiter.resource_address),
+            2u64,
+            604800i64
+        ),
+    );
+    member_approve_recovery(
+        &mut test_runner,
+        app.member.clone(),
+        app.admin.clone(),
+        new_account,
+    );
+    // the threshold is reached 10 days after the first approval
+    test_runner.advance_to_round_at_timestamp(Round::of(1), 1695236716000i64 + 10 * 86400000);
+    member_approve_recovery(
+        &mut test_runner,
+        app.arbiter.clone(),
+        app.admin.clone(),
+        new_account,
+    );
+    member_recover(&mut test_runner, app.member.clone(), app.admin.clone()).expect_commit_failure();
+
+    test_runner.advance_to_round_at_timestamp(Round::of(2), 1695236716000i64 + 17 * 86400000);
+    member_recover(&mut test_runner, app.member.clone(), app.admin.clone()).expect_commit_success();
+    let balance = test_runner.get_component_balance(new_account, app.admin.resource_address);
+    assert!(balance == dec!(1));
+}
+
+#[test]
+fn test_recovery_change_approval() {
+    let (mut test_runner, app) = common::setup_test();
+    let (_, _, new_account) = test_runner.new_allocated_account();
+    common::component_test(
+        &mut test_runner,
+        app.admin.clone(),
+        app.admin.member_component,
+        "set_guardians",
+        manifest_args!(
+            vec!(app.member.resource_address, app.arbiter.resource_address),
+            2u64,
+            0i64
+        ),
+    );
+    // a guardian approving another account doesn't block the recovery
+    member_approve_recovery(
+        &mut test_runner,
+        app.member.clone(),
+        app.admin.clone(),
+        app.member.account_address,
+    );
+    member_approve_recovery(
+        &mut test_runner,
+        app.arbiter.clone(),
+        app.admin.clone(),
+        new_account,
+    );
+    member_recover(&mut test_runner, app.member.clone(), app.admin.clone()).expect_commit_failure();
+    member_approve_recovery(
+        &mut test_runner,
+        app.member.clone(),
+        app.admin.clone(),
+        new_account,
+    );
+
+    member_recover(&mut test_runner, app.member.clone(), app.admin.clone()).expect_commit_success();
+    let balance = test_runner.get_component_balance(new_account, app.admin.resource_address);
+    assert!(balance == dec!(1));
+}
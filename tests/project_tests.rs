@@ -1,12 +1,23 @@
 use diamondpay::project::project_test::ProjectState;
+use diamondpay::types::{
+    Balances, ContractKind, DepositEvent, ListingFee, ListingState, ProjectAction, TxData, TxType,
+    YearData,
+};
 use scrypto_test::prelude::*;
 mod common;
 
+type NftObjectives = HashMap<
+    Decimal,
+    HashMap<ResourceAddress, HashMap<ResourceAddress, IndexSet<NonFungibleLocalId>>>,
+>;
+
 fn create_project(
     test_runner: &mut LedgerSimulator<NoExtension, InMemorySubstateDatabase>,
     package_address: PackageAddress,
     admin: common::MemberData,
     resource_address: ResourceAddress,
+    arbiter_badge: Option<ResourceAddress>,
+    notice_period: i64,
 ) -> ComponentAddress {
     let public_key = admin.public_key;
     let manifest = ManifestBuilder::new()
@@ -30,6 +41,8 @@ fn create_project(
                 1662700716i64,
                 1725859156i64,
                 3u8,
+                arbiter_badge,
+                notice_period,
                 "https://google.com",
                 "Blueprint",
                 HashMap::from([
@@ -57,12 +70,10 @@ fn create_project(
     components[0]
 }
 
-fn project_test(
+fn project_leave(
     test_runner: &mut LedgerSimulator<NoExtension, InMemorySubstateDatabase>,
     member: common::MemberData,
     project_address: ComponentAddress,
-    method_name: &str,
-    args: impl ResolvableArguments,
 ) {
     let public_key = member.public_key;
     let manifest = ManifestBuilder::new()
@@ -72,12 +83,10 @@ fn project_test(
             member.resource_address,
             vec![member.lid.clone()],
         )
-        .call_method(project_address, method_name, args)
-        .call_method(
-            member.account_address,
-            "deposit_batch",
-            manifest_args!(ManifestExpression::EntireWorktop),
-        )
+        .pop_from_auth_zone("proof")
+        .call_method_with_name_lookup(project_address, "leave", |lookup| {
+            (member.resource_address, lookup.proof("proof"))
+        })
         .build();
     let receipt = test_runner.execute_manifest(
         manifest,
@@ -86,7 +95,7 @@ fn project_test(
     receipt.expect_commit_success();
 }
 
-fn project_leave(
+fn project_join(
     test_runner: &mut LedgerSimulator<NoExtension, InMemorySubstateDatabase>,
     member: common::MemberData,
     project_address: ComponentAddress,
@@ -100,9 +109,14 @@ fn project_leave(
             vec![member.lid.clone()],
         )
         .pop_from_auth_zone("proof")
-        .call_method_with_name_lookup(project_address, "leave", |lookup| {
+        .call_method_with_name_lookup(project_address, "join", |lookup| {
             (member.resource_address, lookup.proof("proof"))
         })
+        .call_method(
+            member.account_address,
+            "deposit_batch",
+            manifest_args!(ManifestExpression::EntireWorktop),
+        )
         .build();
     let receipt = test_runner.execute_manifest(
         manifest,
@@ -111,12 +125,14 @@ fn project_leave(
     receipt.expect_commit_success();
 }
 
-fn project_join(
+fn member_call(
     test_runner: &mut LedgerSimulator<NoExtension, InMemorySubstateDatabase>,
     member: common::MemberData,
-    project_address: ComponentAddress,
+    method_name: &str,
+    contract_address: ComponentAddress,
 ) {
     let public_key = member.public_key;
+    // one proof authorizes the member component, the other is forwarded to the contract
     let manifest = ManifestBuilder::new()
         .lock_fee_from_faucet()
         .create_proof_from_account_of_non_fungibles(
@@ -124,9 +140,14 @@ fn project_join(
             member.resource_address,
             vec![member.lid.clone()],
         )
+        .create_proof_from_account_of_non_fungibles(
+            member.account_address,
+            member.resource_address,
+            vec![member.lid.clone()],
+        )
         .pop_from_auth_zone("proof")
-        .call_method_with_name_lookup(project_address, "join", |lookup| {
-            (member.resource_address, lookup.proof("proof"))
+        .call_method_with_name_lookup(member.member_component, method_name, |lookup| {
+            (contract_address, lookup.proof("proof"))
         })
         .call_method(
             member.account_address,
@@ -141,6 +162,35 @@ fn project_join(
     receipt.expect_commit_success();
 }
 
+fn project_apply(
+    test_runner: &mut LedgerSimulator<NoExtension, InMemorySubstateDatabase>,
+    member: common::MemberData,
+    project_address: ComponentAddress,
+) {
+    let public_key = member.public_key;
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .create_proof_from_account_of_non_fungibles(
+            member.account_address,
+            member.resource_address,
+            vec![member.lid.clone()],
+        )
+        .pop_from_auth_zone("proof")
+        .call_method_with_name_lookup(project_address, "apply", |lookup| {
+            (
+                member.resource_address,
+                lookup.proof("proof"),
+                HashMap::from([("message", "Interested in this project")]),
+            )
+        })
+        .build();
+    let receipt = test_runner.execute_manifest(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(&public_key)],
+    );
+    receipt.expect_commit_success();
+}
+
 fn project_deposit(
     test_runner: &mut LedgerSimulator<NoExtension, InMemorySubstateDatabase>,
     member: common::MemberData,
@@ -170,14 +220,103 @@ fn project_deposit(
         manifest,
         vec![NonFungibleGlobalId::from_public_key(&public_key)],
     );
+    let outcome = receipt.expect_commit_success();
+    // every deposit is also emitted as a typed event
+    let (_, data) = outcome
+        .application_events
+        .iter()
+        .find(|(id, _)| test_runner.event_name(id) == "DepositEvent")
+        .unwrap();
+    let event = scrypto_decode::<DepositEvent>(data).unwrap();
+    assert_eq!(event.contract_address, project_address);
+    assert_eq!(event.amount, amount);
+}
+
+fn project_deposit_nfts(
+    test_runner: &mut LedgerSimulator<NoExtension, InMemorySubstateDatabase>,
+    member: common::MemberData,
+    resource_address: ResourceAddress,
+    ids: Vec<NonFungibleLocalId>,
+    project_address: ComponentAddress,
+) {
+    let public_key = member.public_key;
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .create_proof_from_account_of_non_fungibles(
+            member.account_address,
+            member.resource_address,
+            vec![member.lid.clone()],
+        )
+        .call_method(
+            member.account_address,
+            "withdraw_non_fungibles",
+            manifest_args!(resource_address, ids.clone()),
+        )
+        .take_non_fungibles_from_worktop(resource_address, ids, "bucket1")
+        .call_method_with_name_lookup(project_address, "deposit_nfts", |lookup| {
+            (lookup.bucket("bucket1"),)
+        })
+        .build();
+    let receipt = test_runner.execute_manifest(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(&public_key)],
+    );
     receipt.expect_commit_success();
 }
 
+// requests the cancellation, then finalizes it once the clock is past the notice period
 fn project_cancellation(
     test_runner: &mut LedgerSimulator<NoExtension, InMemorySubstateDatabase>,
     member: common::MemberData,
     project_address: ComponentAddress,
+    round: u64,
+    finalize_ms: i64,
 ) {
+    common::component_test(
+        test_runner,
+        member.clone(),
+        project_address,
+        "request_cancellation",
+        manifest_args!(),
+    );
+    test_runner.advance_to_round_at_timestamp(Round::of(round), finalize_ms);
+    project_finalize(test_runner, member, project_address).expect_commit_success();
+}
+
+// requests & finalizes in the same tx, which the notice period must reject
+fn project_request_finalize(
+    test_runner: &mut LedgerSimulator<NoExtension, InMemorySubstateDatabase>,
+    member: common::MemberData,
+    project_address: ComponentAddress,
+) -> TransactionReceipt {
+    let public_key = member.public_key;
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .create_proof_from_account_of_non_fungibles(
+            member.account_address,
+            member.resource_address,
+            vec![member.lid.clone()],
+        )
+        .call_method(project_address, "request_cancellation", manifest_args!())
+        .call_method(project_address, "finalize_cancellation", manifest_args!())
+        .call_method(
+            member.account_address,
+            "deposit_batch",
+            manifest_args!(ManifestExpression::EntireWorktop),
+        )
+        .build();
+    test_runner.execute_manifest(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(&public_key)],
+    )
+}
+
+// returns the receipt so callers can check a finalize before the notice period ends
+fn project_finalize(
+    test_runner: &mut LedgerSimulator<NoExtension, InMemorySubstateDatabase>,
+    member: common::MemberData,
+    project_address: ComponentAddress,
+) -> TransactionReceipt {
     let public_key = member.public_key;
     let manifest = ManifestBuilder::new()
         .lock_fee_from_faucet()
@@ -186,13 +325,44 @@ fn project_cancellation(
             member.resource_address,
             vec![member.lid.clone()],
         )
-        .call_method(project_address, "cancellation", manifest_args!())
+        .call_method(project_address, "finalize_cancellation", manifest_args!())
         .call_method(
             member.account_address,
             "deposit_batch",
             manifest_args!(ManifestExpression::EntireWorktop),
         )
         .build();
+    test_runner.execute_manifest(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(&public_key)],
+    )
+}
+
+fn project_signer<T: ManifestEncode + Clone>(
+    test_runner: &mut LedgerSimulator<NoExtension, InMemorySubstateDatabase>,
+    signer: common::MemberData,
+    project_address: ComponentAddress,
+    method_name: &str,
+    arg: T,
+) {
+    let public_key = signer.public_key;
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .create_proof_from_account_of_non_fungibles(
+            signer.account_address,
+            signer.resource_address,
+            vec![signer.lid.clone()],
+        )
+        .pop_from_auth_zone("proof")
+        .call_method_with_name_lookup(project_address, method_name, |lookup| {
+            (signer.resource_address, lookup.proof("proof"), arg.clone())
+        })
+        .call_method(
+            signer.account_address,
+            "deposit_batch",
+            manifest_args!(ManifestExpression::EntireWorktop),
+        )
+        .build();
     let receipt = test_runner.execute_manifest(
         manifest,
         vec![NonFungibleGlobalId::from_public_key(&public_key)],
@@ -235,6 +405,27 @@ fn project_list(
     member: common::MemberData,
     project_address: ComponentAddress,
     marketplace_address: ComponentAddress,
+    referrer_badge: Option<ResourceAddress>,
+) {
+    project_list_with_fee(
+        test_runner,
+        member,
+        project_address,
+        marketplace_address,
+        referrer_badge,
+        XRD,
+        dec!(100),
+    );
+}
+
+fn project_list_with_fee(
+    test_runner: &mut LedgerSimulator<NoExtension, InMemorySubstateDatabase>,
+    member: common::MemberData,
+    project_address: ComponentAddress,
+    marketplace_address: ComponentAddress,
+    referrer_badge: Option<ResourceAddress>,
+    fee_resource: ResourceAddress,
+    fee_amount: Decimal,
 ) {
     let public_key = member.public_key;
     let manifest = ManifestBuilder::new()
@@ -247,9 +438,9 @@ fn project_list(
         .call_method(
             member.account_address,
             "withdraw",
-            manifest_args!(XRD, dec!(100)), // fee
+            manifest_args!(fee_resource, fee_amount),
         )
-        .take_from_worktop(XRD, dec!(100), "bucket1")
+        .take_from_worktop(fee_resource, fee_amount, "bucket1")
         .call_method(project_address, "list", manifest_args!(marketplace_address))
         .pop_from_auth_zone("proof")
         .call_method_with_name_lookup(marketplace_address, "add_project", |lookup| {
@@ -257,6 +448,7 @@ fn project_list(
                 project_address,
                 lookup.proof("proof"),
                 lookup.bucket("bucket1"),
+                referrer_badge,
             )
         })
         .call_method(
@@ -272,6 +464,95 @@ fn project_list(
     receipt.expect_commit_success();
 }
 
+fn marketplace_claim(
+    test_runner: &mut LedgerSimulator<NoExtension, InMemorySubstateDatabase>,
+    member: common::MemberData,
+    marketplace_address: ComponentAddress,
+) {
+    let public_key = member.public_key;
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .create_proof_from_account_of_non_fungibles(
+            member.account_address,
+            member.resource_address,
+            vec![member.lid.clone()],
+        )
+        .pop_from_auth_zone("proof")
+        .call_method_with_name_lookup(marketplace_address, "claim", |lookup| {
+            (member.resource_address, lookup.proof("proof"))
+        })
+        .call_method(
+            member.account_address,
+            "deposit_batch",
+            manifest_args!(ManifestExpression::EntireWorktop),
+        )
+        .build();
+    let receipt = test_runner.execute_manifest(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(&public_key)],
+    );
+    receipt.expect_commit_success();
+}
+
+// returns the receipt so callers can check a rejected dispute
+fn project_dispute(
+    test_runner: &mut LedgerSimulator<NoExtension, InMemorySubstateDatabase>,
+    member: common::MemberData,
+    project_address: ComponentAddress,
+    obj_number: Decimal,
+) -> TransactionReceipt {
+    let public_key = member.public_key;
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .create_proof_from_account_of_non_fungibles(
+            member.account_address,
+            member.resource_address,
+            vec![member.lid.clone()],
+        )
+        .pop_from_auth_zone("proof")
+        .call_method_with_name_lookup(project_address, "dispute", |lookup| {
+            (obj_number, member.resource_address, lookup.proof("proof"))
+        })
+        .build();
+    test_runner.execute_manifest(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(&public_key)],
+    )
+}
+
+fn project_resolve(
+    test_runner: &mut LedgerSimulator<NoExtension, InMemorySubstateDatabase>,
+    arbiter: common::MemberData,
+    project_address: ComponentAddress,
+    obj_number: Decimal,
+    member_badge: ResourceAddress,
+    member_amounts: HashMap<ResourceAddress, Decimal>,
+) {
+    let public_key = arbiter.public_key;
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .create_proof_from_account_of_non_fungibles(
+            arbiter.account_address,
+            arbiter.resource_address,
+            vec![arbiter.lid.clone()],
+        )
+        .pop_from_auth_zone("proof")
+        .call_method_with_name_lookup(project_address, "resolve", |lookup| {
+            (
+                obj_number,
+                member_badge,
+                member_amounts.clone(),
+                lookup.proof("proof"),
+            )
+        })
+        .build();
+    let receipt = test_runner.execute_manifest(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(&public_key)],
+    );
+    receipt.expect_commit_success();
+}
+
 //
 //
 // Tests
@@ -284,31 +565,32 @@ fn test_members() {
         app.package_address,
         app.admin.clone(),
         app.resource_address,
+        None,
+        1i64,
     );
-    println!("Project Address: {:?}", project_address);
 
-    project_test(
+    common::component_test(
         &mut test_runner,
         app.admin.clone(),
         project_address,
         "invite",
         manifest_args!(app.member.resource_address, "handle_2"),
     );
-    project_test(
+    common::component_test(
         &mut test_runner,
         app.admin.clone(),
         project_address,
         "remove",
         manifest_args!(app.member.resource_address),
     );
-    project_test(
+    common::component_test(
         &mut test_runner,
         app.admin.clone(),
         project_address,
         "invite",
         manifest_args!(app.member.resource_address, "handle_2"),
     );
-    project_test(
+    common::component_test(
         &mut test_runner,
         app.member.clone(),
         app.member.member_component,
@@ -317,6 +599,87 @@ fn test_members() {
     );
     project_join(&mut test_runner, app.member.clone(), project_address);
     project_leave(&mut test_runner, app.member.clone(), project_address);
+
+    project_apply(&mut test_runner, app.member.clone(), project_address);
+    common::component_test(
+        &mut test_runner,
+        app.admin.clone(),
+        project_address,
+        "reject_application",
+        manifest_args!(app.member.resource_address),
+    );
+    project_apply(&mut test_runner, app.member.clone(), project_address);
+    common::component_test(
+        &mut test_runner,
+        app.admin.clone(),
+        project_address,
+        "accept_application",
+        manifest_args!(app.member.resource_address),
+    );
+    let p_state: ProjectState = test_runner.component_state(project_address);
+    assert!(p_state.applications.is_empty());
+    assert!(p_state
+        .member_badges
+        .contains_key(&app.member.resource_address));
+
+    let txs: Vec<TxData> = common::component_call(
+        &mut test_runner,
+        project_address,
+        "txs",
+        manifest_args!(1u64, 20u64),
+    );
+    let tx_types: Vec<TxType> = txs.iter().map(|tx| tx.tx_type.clone()).collect();
+    assert!(
+        tx_types
+            == vec![
+                TxType::Create,
+                TxType::Invite,
+                TxType::Remove,
+                TxType::Invite,
+                TxType::Join,
+                TxType::Remove,
+                TxType::Apply,
+                TxType::Reject,
+                TxType::Apply,
+                TxType::Invite,
+            ]
+    );
+
+    // the range is over tx indexes, so a filtered page can come back short
+    let txs: Vec<TxData> = common::component_call(
+        &mut test_runner,
+        project_address,
+        "txs_by_badge",
+        manifest_args!(app.member.resource_address, 1u64, 5u64),
+    );
+    assert!(txs.len() == 3);
+    let txs: Vec<TxData> = common::component_call(
+        &mut test_runner,
+        project_address,
+        "txs_by_badge",
+        manifest_args!(app.member.resource_address, 1u64, 20u64),
+    );
+    assert!(txs.len() == 9);
+    assert!(txs
+        .iter()
+        .all(|tx| tx.from_badge == app.member.resource_address
+            || tx.to_badge == app.member.resource_address));
+    let txs: Vec<TxData> = common::component_call(
+        &mut test_runner,
+        project_address,
+        "txs_by_type",
+        manifest_args!(TxType::Invite, 1u64, 20u64),
+    );
+    assert!(txs.len() == 3);
+    assert!(txs.iter().all(|tx| tx.tx_type == TxType::Invite));
+
+    let tx_years: Vec<Decimal> = common::component_call(
+        &mut test_runner,
+        project_address,
+        "tx_years",
+        manifest_args!(),
+    );
+    assert!(tx_years == vec![dec!(2023)]);
 }
 
 #[test]
@@ -326,14 +689,30 @@ fn test() {
 
     let (mut test_runner, app) = common::setup_test();
     // Marketplace add market
-    project_test(
+    common::component_test(
         &mut test_runner,
         app.admin.clone(),
         app.marketplace_address,
         "add_category",
-        manifest_args!(vec!["Main"], dec!(2000), dec!(100), app.resource_address),
+        manifest_args!(
+            vec!["Main"],
+            Some(ContractKind::Project),
+            dec!(2000),
+            HashMap::from([
+                (XRD, ListingFee::Flat(dec!(100))),
+                (
+                    app.resource_address,
+                    ListingFee::Percent {
+                        percent: dec!(1),
+                        min: Some(dec!(10)),
+                        max: Some(dec!(100)),
+                    }
+                ),
+            ]),
+            app.resource_address
+        ),
     );
-    project_test(
+    common::component_test(
         &mut test_runner,
         app.admin.clone(),
         app.marketplace_address,
@@ -342,6 +721,9 @@ fn test() {
             "Main",
             true,
             dec!(3000),
+            HashMap::from([(XRD, ListingFee::Flat(dec!(50)))]),
+            app.resource_address,
+            true,
             HashMap::from([("description", "A description")])
         ),
     );
@@ -351,10 +733,11 @@ fn test() {
         app.package_address,
         app.admin.clone(),
         app.resource_address,
+        None,
+        1i64,
     );
-    println!("Project Address: {:?}", project_address);
 
-    project_test(
+    common::component_test(
         &mut test_runner,
         app.admin.clone(),
         project_address,
@@ -363,7 +746,7 @@ fn test() {
     );
     project_join(&mut test_runner, app.admin.clone(), project_address);
 
-    project_test(
+    common::component_test(
         &mut test_runner,
         app.admin.clone(),
         project_address,
@@ -371,7 +754,7 @@ fn test() {
         manifest_args!(app.member.resource_address, "handle_2"),
     );
     project_join(&mut test_runner, app.member.clone(), project_address);
-    project_test(
+    common::component_test(
         &mut test_runner,
         app.member.clone(),
         app.member.member_component,
@@ -390,48 +773,78 @@ fn test() {
         (
             dec!(1),
             HashMap::from([
-                (app.admin.resource_address, dec!(100)),
-                (app.member.resource_address, dec!(200)),
+                (
+                    app.admin.resource_address,
+                    HashMap::from([(app.resource_address, dec!(100))]),
+                ),
+                (
+                    app.member.resource_address,
+                    HashMap::from([(app.resource_address, dec!(200))]),
+                ),
             ]),
         ),
         (
             dec!(2),
             HashMap::from([
-                (app.admin.resource_address, dec!(200)),
-                (app.member.resource_address, dec!(400)),
+                (
+                    app.admin.resource_address,
+                    HashMap::from([(app.resource_address, dec!(200))]),
+                ),
+                (
+                    app.member.resource_address,
+                    HashMap::from([(app.resource_address, dec!(400))]),
+                ),
             ]),
         ),
         (
             dec!(3),
             HashMap::from([
-                (app.admin.resource_address, dec!(100)),
-                (app.member.resource_address, dec!(200)),
+                (
+                    app.admin.resource_address,
+                    HashMap::from([(app.resource_address, dec!(100))]),
+                ),
+                (
+                    app.member.resource_address,
+                    HashMap::from([(app.resource_address, dec!(200))]),
+                ),
             ]),
         ),
         (
             dec!(4),
             HashMap::from([
-                (app.admin.resource_address, dec!(200)),
-                (app.member.resource_address, dec!(400)),
+                (
+                    app.admin.resource_address,
+                    HashMap::from([(app.resource_address, dec!(200))]),
+                ),
+                (
+                    app.member.resource_address,
+                    HashMap::from([(app.resource_address, dec!(400))]),
+                ),
             ]),
         ),
         (
             dec!(5),
-            HashMap::from([(app.member.resource_address, dec!(200))]),
+            HashMap::from([(
+                app.member.resource_address,
+                HashMap::from([(app.resource_address, dec!(200))]),
+            )]),
         ),
         (
             dec!(6),
-            HashMap::from([(app.member.resource_address, dec!(1000))]),
+            HashMap::from([(
+                app.member.resource_address,
+                HashMap::from([(app.resource_address, dec!(1000))]),
+            )]),
         ),
     ]);
-    project_test(
+    common::component_test(
         &mut test_runner,
         app.admin.clone(),
         project_address,
         "update",
-        manifest_args!(objs),
+        manifest_args!(objs, NftObjectives::new()),
     );
-    project_test(
+    common::component_test(
         &mut test_runner,
         app.admin.clone(),
         project_address,
@@ -445,47 +858,164 @@ fn test() {
         ),
     );
     if is_list {
-        project_list(
+        common::component_test(
             &mut test_runner,
             app.admin.clone(),
-            project_address,
             app.marketplace_address,
+            "set_fee_shares",
+            manifest_args!(dec!(10), dec!(20)),
         );
-        project_test(
+        common::component_test(
             &mut test_runner,
             app.admin.clone(),
             app.marketplace_address,
-            "withdraw",
-            manifest_args!(XRD),
+            "set_curator",
+            manifest_args!("Blueprint", true, Some(app.arbiter.resource_address)),
         );
-        project_test(
+        project_list(
             &mut test_runner,
             app.admin.clone(),
+            project_address,
             app.marketplace_address,
-            "remove_contract",
-            manifest_args!(project_address, "Blueprint", true),
+            Some(app.member.resource_address),
         );
-    }
-
-    project_test(
-        &mut test_runner,
-        app.admin.clone(),
-        project_address,
-        "reward",
-        manifest_args!(dec!(1)),
-    );
+        let contracts: Vec<ComponentAddress> = common::component_call(
+            &mut test_runner,
+            app.marketplace_address,
+            "contracts",
+            manifest_args!("Blueprint", true, 1u64, 10u64),
+        );
+        assert!(contracts == vec![project_address]);
+        let counts: (Decimal, Decimal) = common::component_call(
+            &mut test_runner,
+            app.marketplace_address,
+            "contract_counts",
+            manifest_args!("Blueprint", true),
+        );
+        assert!(counts == (dec!(1), dec!(0)));
+        // 10% of the 100 XRD fee to the referrer & 20% to the curator
+        let earnings: HashMap<ResourceAddress, Decimal> = common::component_call(
+            &mut test_runner,
+            app.marketplace_address,
+            "earnings",
+            manifest_args!(app.member.resource_address),
+        );
+        assert!(earnings == HashMap::from([(XRD, dec!(10))]));
+        let earnings: HashMap<ResourceAddress, Decimal> = common::component_call(
+            &mut test_runner,
+            app.marketplace_address,
+            "earnings",
+            manifest_args!(app.arbiter.resource_address),
+        );
+        assert!(earnings == HashMap::from([(XRD, dec!(20))]));
+
+        let member_balance = test_runner.get_component_balance(app.member.account_address, XRD);
+        let arbiter_balance = test_runner.get_component_balance(app.arbiter.account_address, XRD);
+        marketplace_claim(
+            &mut test_runner,
+            app.member.clone(),
+            app.marketplace_address,
+        );
+        marketplace_claim(
+            &mut test_runner,
+            app.arbiter.clone(),
+            app.marketplace_address,
+        );
+        let claimed =
+            test_runner.get_component_balance(app.member.account_address, XRD) - member_balance;
+        assert!(claimed == dec!(10));
+        let claimed =
+            test_runner.get_component_balance(app.arbiter.account_address, XRD) - arbiter_balance;
+        assert!(claimed == dec!(20));
+        let earnings: HashMap<ResourceAddress, Decimal> = common::component_call(
+            &mut test_runner,
+            app.marketplace_address,
+            "earnings",
+            manifest_args!(app.member.resource_address),
+        );
+        assert!(earnings.is_empty());
+        // the marketplace keeps what is left of the fee after the shares
+        let balances: Balances = common::component_call(
+            &mut test_runner,
+            app.marketplace_address,
+            "balances",
+            manifest_args!(),
+        );
+        assert!(balances.fungibles == HashMap::from([(XRD, dec!(70))]));
+        assert!(balances.non_fungibles.is_empty());
+        common::component_test(
+            &mut test_runner,
+            app.admin.clone(),
+            app.marketplace_address,
+            "withdraw_amount",
+            manifest_args!(XRD, dec!(10)),
+        );
+        common::component_test(
+            &mut test_runner,
+            app.admin.clone(),
+            app.marketplace_address,
+            "withdraw",
+            manifest_args!(XRD),
+        );
+        common::component_test(
+            &mut test_runner,
+            app.admin.clone(),
+            app.marketplace_address,
+            "rename_category",
+            manifest_args!("Blueprint", "Blueprints", true),
+        );
+        // Listed contracts still resolve through the old name
+        common::component_test(
+            &mut test_runner,
+            app.admin.clone(),
+            app.marketplace_address,
+            "remove_contract",
+            manifest_args!(project_address, "Blueprint", true),
+        );
+        let p_state: ProjectState = test_runner.component_state(project_address);
+        assert!(p_state.marketplaces.is_empty());
+        let contracts: Vec<ComponentAddress> = common::component_call(
+            &mut test_runner,
+            app.marketplace_address,
+            "contracts",
+            manifest_args!("Blueprints", true, 1u64, 10u64),
+        );
+        assert!(contracts.is_empty());
+        let counts: (Decimal, Decimal) = common::component_call(
+            &mut test_runner,
+            app.marketplace_address,
+            "contract_counts",
+            manifest_args!("Blueprints", true),
+        );
+        assert!(counts == (dec!(0), dec!(1)));
+        common::component_test(
+            &mut test_runner,
+            app.admin.clone(),
+            app.marketplace_address,
+            "remove_category",
+            manifest_args!("Main", true, None::<String>),
+        );
+    }
+
+    common::component_test(
+        &mut test_runner,
+        app.admin.clone(),
+        project_address,
+        "reward",
+        manifest_args!(dec!(1)),
+    );
     if !is_list {
         project_withdraw(&mut test_runner, app.admin.clone(), project_address);
         project_withdraw(&mut test_runner, app.member.clone(), project_address);
     }
-    project_test(
+    common::component_test(
         &mut test_runner,
         app.admin.clone(),
         project_address,
         "reward",
         manifest_args!(dec!(2)),
     );
-    project_test(
+    common::component_test(
         &mut test_runner,
         app.admin.clone(),
         project_address,
@@ -494,7 +1024,13 @@ fn test() {
     );
 
     if !is_list {
-        project_cancellation(&mut test_runner, app.admin.clone(), project_address);
+        project_cancellation(
+            &mut test_runner,
+            app.admin.clone(),
+            project_address,
+            1,
+            1695236716000i64 + 1000,
+        );
         project_withdraw(&mut test_runner, app.admin.clone(), project_address);
         project_withdraw(&mut test_runner, app.member.clone(), project_address);
     }
@@ -504,3 +1040,1119 @@ fn test() {
     assert!(p_state.completed.contains_key(&dec!(2)));
     assert!(p_state.completed.contains_key(&dec!(3)));
 }
+
+#[test]
+fn test_dispute() {
+    let (mut test_runner, app) = common::setup_test();
+    let project_address = create_project(
+        &mut test_runner,
+        app.package_address,
+        app.admin.clone(),
+        app.resource_address,
+        Some(app.arbiter.resource_address),
+        1i64,
+    );
+
+    common::component_test(
+        &mut test_runner,
+        app.admin.clone(),
+        project_address,
+        "invite",
+        manifest_args!(app.member.resource_address, "handle_2"),
+    );
+    project_join(&mut test_runner, app.member.clone(), project_address);
+    project_deposit(
+        &mut test_runner,
+        app.admin.clone(),
+        app.resource_address,
+        dec!(1000),
+        project_address,
+    );
+    let objs = HashMap::from([
+        (
+            dec!(1),
+            HashMap::from([(
+                app.member.resource_address,
+                HashMap::from([(app.resource_address, dec!(400))]),
+            )]),
+        ),
+        (
+            dec!(2),
+            HashMap::from([(
+                app.member.resource_address,
+                HashMap::from([(app.resource_address, dec!(600))]),
+            )]),
+        ),
+    ]);
+    common::component_test(
+        &mut test_runner,
+        app.admin.clone(),
+        project_address,
+        "update",
+        manifest_args!(objs, NftObjectives::new()),
+    );
+
+    // Objectives can be disputed once a cancellation is pending
+    common::component_test(
+        &mut test_runner,
+        app.admin.clone(),
+        project_address,
+        "request_cancellation",
+        manifest_args!(),
+    );
+
+    // Freeze objective 1 so cancellation can't sweep it
+    project_dispute(
+        &mut test_runner,
+        app.member.clone(),
+        project_address,
+        dec!(1),
+    )
+    .expect_commit_success();
+    // the member can't be removed while the dispute is open
+    common::component_receipt(
+        &mut test_runner,
+        app.admin.clone(),
+        project_address,
+        "remove",
+        manifest_args!(app.member.resource_address),
+    )
+    .expect_commit_failure();
+    test_runner.advance_to_round_at_timestamp(Round::of(1), 1695236716000i64 + 1000);
+    common::component_test(
+        &mut test_runner,
+        app.admin.clone(),
+        project_address,
+        "finalize_cancellation",
+        manifest_args!(),
+    );
+    let p_state: ProjectState = test_runner.component_state(project_address);
+    assert!(p_state.disputes.contains_key(&dec!(1)));
+    assert!(!p_state.objectives.contains_key(&dec!(1)));
+
+    // Split the frozen amount between the member & the admin
+    project_resolve(
+        &mut test_runner,
+        app.arbiter.clone(),
+        project_address,
+        dec!(1),
+        app.member.resource_address,
+        HashMap::from([(app.resource_address, dec!(300))]),
+    );
+    project_withdraw(&mut test_runner, app.member.clone(), project_address);
+
+    let p_state: ProjectState = test_runner.component_state(project_address);
+    assert!(p_state.disputes.is_empty());
+    assert!(*p_state.rewarded.get(&app.resource_address).unwrap() == dec!(300));
+
+    // The admin's share is held for the admin since the funds were already swept
+    common::component_test(
+        &mut test_runner,
+        app.admin.clone(),
+        project_address,
+        "withdraw_refunds",
+        manifest_args!(),
+    );
+    let balance =
+        test_runner.get_component_balance(app.admin.account_address, app.resource_address);
+    assert!(balance == dec!(10700));
+
+    // the member's share counts as rewarded, the refund & the swept funds as cancelled
+    let year_data: Option<YearData> = common::component_call(
+        &mut test_runner,
+        project_address,
+        "tx_year",
+        manifest_args!(dec!(2023)),
+    );
+    let year_data = year_data.unwrap();
+    let resource = app.resource_address;
+    assert!(year_data.deposited == HashMap::from([(resource, dec!(1000))]));
+    assert!(year_data.rewarded == HashMap::from([(resource, dec!(300))]));
+    assert!(year_data.withdrawn == HashMap::from([(resource, dec!(300))]));
+    assert!(year_data.cancelled == HashMap::from([(resource, dec!(700))]));
+}
+
+#[test]
+fn test_listing_states() {
+    let (mut test_runner, app) = common::setup_test();
+    let project_address = create_project(
+        &mut test_runner,
+        app.package_address,
+        app.admin.clone(),
+        app.resource_address,
+        None,
+        1i64,
+    );
+    project_deposit(
+        &mut test_runner,
+        app.admin.clone(),
+        app.resource_address,
+        dec!(1000),
+        project_address,
+    );
+    project_list(
+        &mut test_runner,
+        app.admin.clone(),
+        project_address,
+        app.marketplace_address,
+        None,
+    );
+    let state: Option<ListingState> = common::component_call(
+        &mut test_runner,
+        app.marketplace_address,
+        "contract_state",
+        manifest_args!("Blueprint", true, project_address),
+    );
+    assert!(state == Some(ListingState::Active));
+
+    // Reaching max members fills the listing
+    for member in [app.admin.clone(), app.member.clone(), app.arbiter.clone()] {
+        common::component_test(
+            &mut test_runner,
+            app.admin.clone(),
+            project_address,
+            "invite",
+            manifest_args!(member.resource_address, member.handle.clone()),
+        );
+    }
+    let state: Option<ListingState> = common::component_call(
+        &mut test_runner,
+        app.marketplace_address,
+        "contract_state",
+        manifest_args!("Blueprint", true, project_address),
+    );
+    assert!(state == Some(ListingState::Filled));
+    let contracts: Vec<ComponentAddress> = common::component_call(
+        &mut test_runner,
+        app.marketplace_address,
+        "contracts",
+        manifest_args!("Blueprint", true, 1u64, 10u64),
+    );
+    assert!(contracts.is_empty());
+
+    // Raising max members reopens it
+    common::component_test(
+        &mut test_runner,
+        app.admin.clone(),
+        project_address,
+        "details",
+        manifest_args!(
+            1662700716i64,
+            1725859156i64,
+            5u8,
+            "https://google.com",
+            HashMap::from([("obj_names", "")])
+        ),
+    );
+    let state: Option<ListingState> = common::component_call(
+        &mut test_runner,
+        app.marketplace_address,
+        "contract_state",
+        manifest_args!("Blueprint", true, project_address),
+    );
+    assert!(state == Some(ListingState::Active));
+    let contracts: Vec<ComponentAddress> = common::component_call(
+        &mut test_runner,
+        app.marketplace_address,
+        "contracts",
+        manifest_args!("Blueprint", true, 1u64, 10u64),
+    );
+    assert!(contracts == vec![project_address]);
+
+    // Cancelling closes it
+    test_runner.advance_to_round_at_timestamp(Round::of(1), 1695236716000i64 + 6 * 86400000);
+    project_cancellation(
+        &mut test_runner,
+        app.admin.clone(),
+        project_address,
+        2,
+        1695236716000i64 + 6 * 86400000 + 1000,
+    );
+    let state: Option<ListingState> = common::component_call(
+        &mut test_runner,
+        app.marketplace_address,
+        "contract_state",
+        manifest_args!("Blueprint", true, project_address),
+    );
+    assert!(state == Some(ListingState::Closed));
+    let counts: (Decimal, Decimal) = common::component_call(
+        &mut test_runner,
+        app.marketplace_address,
+        "contract_counts",
+        manifest_args!("Blueprint", true),
+    );
+    assert!(counts == (dec!(0), dec!(1)));
+}
+
+#[test]
+fn test_category_migration() {
+    let (mut test_runner, app) = common::setup_test();
+    common::component_test(
+        &mut test_runner,
+        app.admin.clone(),
+        app.marketplace_address,
+        "add_category",
+        manifest_args!(
+            vec!["Main"],
+            Some(ContractKind::Project),
+            dec!(100),
+            HashMap::from([(XRD, ListingFee::Flat(dec!(100)))]),
+            app.resource_address
+        ),
+    );
+    let project_address = create_project(
+        &mut test_runner,
+        app.package_address,
+        app.admin.clone(),
+        app.resource_address,
+        None,
+        1i64,
+    );
+    project_deposit(
+        &mut test_runner,
+        app.admin.clone(),
+        app.resource_address,
+        dec!(1000),
+        project_address,
+    );
+    project_list(
+        &mut test_runner,
+        app.admin.clone(),
+        project_address,
+        app.marketplace_address,
+        None,
+    );
+    for member in [app.admin.clone(), app.member.clone(), app.arbiter.clone()] {
+        common::component_test(
+            &mut test_runner,
+            app.admin.clone(),
+            project_address,
+            "invite",
+            manifest_args!(member.resource_address, member.handle.clone()),
+        );
+    }
+
+    // The filled listing is moved in its current state
+    common::component_test(
+        &mut test_runner,
+        app.admin.clone(),
+        app.marketplace_address,
+        "remove_category",
+        manifest_args!("Blueprint", true, Some("Main")),
+    );
+    common::component_test(
+        &mut test_runner,
+        app.admin.clone(),
+        app.marketplace_address,
+        "migrate_contracts",
+        manifest_args!("Blueprint", true, 1u64, 10u64),
+    );
+    // re-running a migrated page skips its listings
+    common::component_test(
+        &mut test_runner,
+        app.admin.clone(),
+        app.marketplace_address,
+        "migrate_contracts",
+        manifest_args!("Blueprint", true, 1u64, 10u64),
+    );
+    let state: Option<ListingState> = common::component_call(
+        &mut test_runner,
+        app.marketplace_address,
+        "contract_state",
+        manifest_args!("Main", true, project_address),
+    );
+    assert!(state == Some(ListingState::Filled));
+    let counts: (Decimal, Decimal) = common::component_call(
+        &mut test_runner,
+        app.marketplace_address,
+        "contract_counts",
+        manifest_args!("Main", true),
+    );
+    assert!(counts == (dec!(0), dec!(1)));
+    let counts: (Decimal, Decimal) = common::component_call(
+        &mut test_runner,
+        app.marketplace_address,
+        "contract_counts",
+        manifest_args!("Blueprint", true),
+    );
+    assert!(counts == (dec!(0), dec!(1)));
+
+    // Reopening restores it in the new category only
+    common::component_test(
+        &mut test_runner,
+        app.admin.clone(),
+        project_address,
+        "remove",
+        manifest_args!(app.arbiter.resource_address),
+    );
+    let contracts: Vec<ComponentAddress> = common::component_call(
+        &mut test_runner,
+        app.marketplace_address,
+        "contracts",
+        manifest_args!("Main", true, 1u64, 10u64),
+    );
+    assert!(contracts == vec![project_address]);
+    let contracts: Vec<ComponentAddress> = common::component_call(
+        &mut test_runner,
+        app.marketplace_address,
+        "contracts",
+        manifest_args!("Blueprint", true, 1u64, 10u64),
+    );
+    assert!(contracts.is_empty());
+}
+
+#[test]
+fn test_delist_cancelled() {
+    let (mut test_runner, app) = common::setup_test();
+    let project_address = create_project(
+        &mut test_runner,
+        app.package_address,
+        app.admin.clone(),
+        app.resource_address,
+        None,
+        1i64,
+    );
+    project_deposit(
+        &mut test_runner,
+        app.admin.clone(),
+        app.resource_address,
+        dec!(1000),
+        project_address,
+    );
+    project_list(
+        &mut test_runner,
+        app.admin.clone(),
+        project_address,
+        app.marketplace_address,
+        None,
+    );
+
+    // Cancel after the listing lock period, which closes the listing
+    test_runner.advance_to_round_at_timestamp(Round::of(1), 1695236716000i64 + 6 * 86400000);
+    project_cancellation(
+        &mut test_runner,
+        app.admin.clone(),
+        project_address,
+        2,
+        1695236716000i64 + 6 * 86400000 + 1000,
+    );
+    let state: Option<ListingState> = common::component_call(
+        &mut test_runner,
+        app.marketplace_address,
+        "contract_state",
+        manifest_args!("Blueprint", true, project_address),
+    );
+    assert!(state == Some(ListingState::Closed));
+
+    // Anyone can delist a cancelled contract without the admin's proof
+    common::component_test(
+        &mut test_runner,
+        app.member.clone(),
+        app.marketplace_address,
+        "delist_project",
+        manifest_args!(project_address, None::<ManifestProof>),
+    );
+    let p_state: ProjectState = test_runner.component_state(project_address);
+    assert!(p_state.marketplaces.is_empty());
+    let counts: (Decimal, Decimal) = common::component_call(
+        &mut test_runner,
+        app.marketplace_address,
+        "contract_counts",
+        manifest_args!("Blueprint", true),
+    );
+    assert!(counts == (dec!(0), dec!(1)));
+}
+
+#[test]
+fn test_member_custody() {
+    let (mut test_runner, app) = common::setup_test();
+    let project_address = create_project(
+        &mut test_runner,
+        app.package_address,
+        app.admin.clone(),
+        app.resource_address,
+        None,
+        1i64,
+    );
+
+    common::component_test(
+        &mut test_runner,
+        app.admin.clone(),
+        project_address,
+        "invite",
+        manifest_args!(app.member.resource_address, "handle_2"),
+    );
+    common::component_test(
+        &mut test_runner,
+        app.member.clone(),
+        app.member.member_component,
+        "set_custody",
+        manifest_args!(true),
+    );
+    member_call(
+        &mut test_runner,
+        app.member.clone(),
+        "join_project",
+        project_address,
+    );
+    project_deposit(
+        &mut test_runner,
+        app.admin.clone(),
+        app.resource_address,
+        dec!(1000),
+        project_address,
+    );
+    let objs = HashMap::from([(
+        dec!(1),
+        HashMap::from([(
+            app.member.resource_address,
+            HashMap::from([(app.resource_address, dec!(400))]),
+        )]),
+    )]);
+    common::component_test(
+        &mut test_runner,
+        app.admin.clone(),
+        project_address,
+        "update",
+        manifest_args!(objs, NftObjectives::new()),
+    );
+    common::component_test(
+        &mut test_runner,
+        app.admin.clone(),
+        project_address,
+        "reward",
+        manifest_args!(dec!(1)),
+    );
+    // the custodied project badge proves the withdrawal
+    common::component_test(
+        &mut test_runner,
+        app.member.clone(),
+        app.member.member_component,
+        "withdraw_from_project",
+        manifest_args!(project_address),
+    );
+    // the reward & the custodied project badge are reported separately
+    let balances: Balances = common::component_call(
+        &mut test_runner,
+        app.member.member_component,
+        "balances",
+        manifest_args!(),
+    );
+    assert!(balances.fungibles == HashMap::from([(app.resource_address, dec!(400))]));
+    let badge_ids: Vec<&IndexSet<NonFungibleLocalId>> = balances.non_fungibles.values().collect();
+    assert!(badge_ids == vec![&indexset!(NonFungibleLocalId::string("handle_2").unwrap())]);
+
+    let p_state: ProjectState = test_runner.component_state(project_address);
+    assert!(*p_state.withdrawn.get(&app.resource_address).unwrap() == dec!(400));
+}
+
+#[test]
+fn test_multisig() {
+    let (mut test_runner, app) = common::setup_test();
+    let project_address = create_project(
+        &mut test_runner,
+        app.package_address,
+        app.admin.clone(),
+        app.resource_address,
+        None,
+        1i64,
+    );
+    project_deposit(
+        &mut test_runner,
+        app.admin.clone(),
+        app.resource_address,
+        dec!(1000),
+        project_address,
+    );
+    common::component_test(
+        &mut test_runner,
+        app.admin.clone(),
+        project_address,
+        "set_multisig",
+        manifest_args!(
+            vec!(app.admin.resource_address, app.arbiter.resource_address),
+            2u64
+        ),
+    );
+
+    // admin methods require a proposal, deposits a signer
+    common::component_receipt(
+        &mut test_runner,
+        app.admin.clone(),
+        project_address,
+        "invite",
+        manifest_args!(app.member.resource_address, app.member.handle.clone()),
+    )
+    .expect_commit_failure();
+    common::component_receipt(
+        &mut test_runner,
+        app.admin.clone(),
+        project_address,
+        "list",
+        manifest_args!(app.marketplace_address),
+    )
+    .expect_commit_failure();
+    let signer = app.admin.clone();
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .create_proof_from_account_of_non_fungibles(
+            signer.account_address,
+            signer.resource_address,
+            vec![signer.lid.clone()],
+        )
+        .pop_from_auth_zone("proof")
+        .call_method(
+            signer.account_address,
+            "withdraw",
+            manifest_args!(app.resource_address, dec!(500)),
+        )
+        .take_from_worktop(app.resource_address, dec!(500), "funds")
+        .call_method_with_name_lookup(project_address, "signer_deposit", |lookup| {
+            (
+                signer.resource_address,
+                lookup.proof("proof"),
+                lookup.bucket("funds"),
+            )
+        })
+        .build();
+    test_runner
+        .execute_manifest(
+            manifest,
+            vec![NonFungibleGlobalId::from_public_key(&signer.public_key)],
+        )
+        .expect_commit_success();
+
+    // the signers rotate the arbiter out for the member
+    project_signer(
+        &mut test_runner,
+        app.admin.clone(),
+        project_address,
+        "propose",
+        ProjectAction::SetSigners(
+            vec![app.admin.resource_address, app.member.resource_address],
+            2,
+        ),
+    );
+    project_signer(
+        &mut test_runner,
+        app.arbiter.clone(),
+        project_address,
+        "approve",
+        dec!(1),
+    );
+    project_signer(
+        &mut test_runner,
+        app.admin.clone(),
+        project_address,
+        "execute",
+        dec!(1),
+    );
+    let signers: Option<(HashSet<ResourceAddress>, u64)> = common::component_call(
+        &mut test_runner,
+        project_address,
+        "signers",
+        manifest_args!(),
+    );
+    assert!(
+        signers
+            == Some((
+                HashSet::from([app.admin.resource_address, app.member.resource_address]),
+                2
+            ))
+    );
+
+    project_signer(
+        &mut test_runner,
+        app.admin.clone(),
+        project_address,
+        "propose",
+        ProjectAction::Cancellation,
+    );
+    project_signer(
+        &mut test_runner,
+        app.member.clone(),
+        project_address,
+        "approve",
+        dec!(2),
+    );
+    project_signer(
+        &mut test_runner,
+        app.admin.clone(),
+        project_address,
+        "execute",
+        dec!(2),
+    );
+    // the approved request starts the notice period, finalizing sweeps the funds
+    let p_state: ProjectState = test_runner.component_state(project_address);
+    assert!(!p_state.is_cancelled);
+    common::component_receipt(
+        &mut test_runner,
+        app.admin.clone(),
+        project_address,
+        "finalize_cancellation",
+        manifest_args!(),
+    )
+    .expect_commit_failure();
+    project_signer(
+        &mut test_runner,
+        app.member.clone(),
+        project_address,
+        "propose",
+        ProjectAction::FinalizeCancellation,
+    );
+    project_signer(
+        &mut test_runner,
+        app.admin.clone(),
+        project_address,
+        "approve",
+        dec!(3),
+    );
+    // the funds go to the admin's Member, not to the signer executing
+    let balance =
+        test_runner.get_component_balance(app.member.account_address, app.resource_address);
+    test_runner.advance_to_round_at_timestamp(Round::of(1), 1695236716000i64 + 1000);
+    project_signer(
+        &mut test_runner,
+        app.member.clone(),
+        project_address,
+        "execute",
+        dec!(3),
+    );
+
+    let p_state: ProjectState = test_runner.component_state(project_address);
+    assert!(p_state.is_cancelled);
+    let member_balance =
+        test_runner.get_component_balance(app.member.account_address, app.resource_address);
+    assert!(member_balance == balance);
+    let balances: Balances = common::component_call(
+        &mut test_runner,
+        app.admin.member_component,
+        "balances",
+        manifest_args!(),
+    );
+    assert!(balances.fungibles == HashMap::from([(app.resource_address, dec!(1500))]));
+
+    // proposal txs record the proposal id, not an amount
+    let txs: Vec<TxData> = common::component_call(
+        &mut test_runner,
+        project_address,
+        "txs_by_badge",
+        manifest_args!(app.member.resource_address, 1u64, 20u64),
+    );
+    let proposal_txs: Vec<(TxType, Option<Decimal>, Decimal)> = txs
+        .iter()
+        .map(|tx| (tx.tx_type.clone(), tx.proposal_id, tx.amount))
+        .collect();
+    assert!(
+        proposal_txs
+            == vec![
+                (TxType::Approve, Some(dec!(2)), dec!(0)),
+                (TxType::Propose, Some(dec!(3)), dec!(0)),
+            ]
+    );
+}
+
+#[test]
+fn test_multi_resource() {
+    let (mut test_runner, app) = common::setup_test();
+    let project_address = create_project(
+        &mut test_runner,
+        app.package_address,
+        app.admin.clone(),
+        app.resource_address,
+        None,
+        1i64,
+    );
+
+    common::component_test(
+        &mut test_runner,
+        app.admin.clone(),
+        project_address,
+        "invite",
+        manifest_args!(app.member.resource_address, "handle_2"),
+    );
+    project_join(&mut test_runner, app.member.clone(), project_address);
+    project_deposit(
+        &mut test_runner,
+        app.admin.clone(),
+        app.resource_address,
+        dec!(1000),
+        project_address,
+    );
+    project_deposit(
+        &mut test_runner,
+        app.admin.clone(),
+        XRD,
+        dec!(500),
+        project_address,
+    );
+    let objs = HashMap::from([
+        (
+            dec!(1),
+            HashMap::from([(
+                app.member.resource_address,
+                HashMap::from([(app.resource_address, dec!(400)), (XRD, dec!(200))]),
+            )]),
+        ),
+        (
+            dec!(2),
+            HashMap::from([(
+                app.member.resource_address,
+                HashMap::from([(app.resource_address, dec!(600)), (XRD, dec!(300))]),
+            )]),
+        ),
+    ]);
+    common::component_test(
+        &mut test_runner,
+        app.admin.clone(),
+        project_address,
+        "update",
+        manifest_args!(objs, NftObjectives::new()),
+    );
+    common::component_test(
+        &mut test_runner,
+        app.admin.clone(),
+        project_address,
+        "reward",
+        manifest_args!(dec!(1)),
+    );
+    project_withdraw(&mut test_runner, app.member.clone(), project_address);
+    // the unrewarded objective is refunded in both resources
+    project_cancellation(
+        &mut test_runner,
+        app.admin.clone(),
+        project_address,
+        1,
+        1695236716000i64 + 1000,
+    );
+
+    let p_state: ProjectState = test_runner.component_state(project_address);
+    assert!(*p_state.withdrawn.get(&app.resource_address).unwrap() == dec!(400));
+    assert!(*p_state.withdrawn.get(&XRD).unwrap() == dec!(200));
+    assert!(p_state.is_cancelled);
+
+    // year totals are kept apart by resource
+    let year_data: Option<YearData> = common::component_call(
+        &mut test_runner,
+        project_address,
+        "tx_year",
+        manifest_args!(dec!(2023)),
+    );
+    let year_data = year_data.unwrap();
+    let resource = app.resource_address;
+    assert!(year_data.deposited == HashMap::from([(resource, dec!(1000)), (XRD, dec!(500))]));
+    assert!(year_data.rewarded == HashMap::from([(resource, dec!(400)), (XRD, dec!(200))]));
+    assert!(year_data.withdrawn == HashMap::from([(resource, dec!(400)), (XRD, dec!(200))]));
+    assert!(year_data.cancelled == HashMap::from([(resource, dec!(600)), (XRD, dec!(300))]));
+}
+
+#[test]
+fn test_nft_rewards() {
+    let (mut test_runner, app) = common::setup_test();
+    let nft_address = test_runner.create_non_fungible_resource(app.admin.account_address);
+    let project_address = create_project(
+        &mut test_runner,
+        app.package_address,
+        app.admin.clone(),
+        app.resource_address,
+        Some(app.arbiter.resource_address),
+        1i64,
+    );
+
+    common::component_test(
+        &mut test_runner,
+        app.admin.clone(),
+        project_address,
+        "invite",
+        manifest_args!(app.member.resource_address, "handle_2"),
+    );
+    project_join(&mut test_runner, app.member.clone(), project_address);
+    project_deposit(
+        &mut test_runner,
+        app.admin.clone(),
+        app.resource_address,
+        dec!(1000),
+        project_address,
+    );
+    project_deposit_nfts(
+        &mut test_runner,
+        app.admin.clone(),
+        nft_address,
+        vec![
+            NonFungibleLocalId::integer(1),
+            NonFungibleLocalId::integer(2),
+            NonFungibleLocalId::integer(3),
+        ],
+        project_address,
+    );
+    let objs = HashMap::from([(
+        dec!(1),
+        HashMap::from([(
+            app.member.resource_address,
+            HashMap::from([(app.resource_address, dec!(1000))]),
+        )]),
+    )]);
+    let nft_objs: NftObjectives = HashMap::from([
+        (
+            dec!(1),
+            HashMap::from([(
+                app.member.resource_address,
+                HashMap::from([(nft_address, indexset!(NonFungibleLocalId::integer(1)))]),
+            )]),
+        ),
+        (
+            dec!(2),
+            HashMap::from([(
+                app.member.resource_address,
+                HashMap::from([(
+                    nft_address,
+                    indexset!(
+                        NonFungibleLocalId::integer(2),
+                        NonFungibleLocalId::integer(3)
+                    ),
+                )]),
+            )]),
+        ),
+    ]);
+    common::component_test(
+        &mut test_runner,
+        app.admin.clone(),
+        project_address,
+        "update",
+        manifest_args!(objs, nft_objs),
+    );
+    common::component_test(
+        &mut test_runner,
+        app.admin.clone(),
+        project_address,
+        "reward",
+        manifest_args!(dec!(1)),
+    );
+    project_withdraw(&mut test_runner, app.member.clone(), project_address);
+
+    // escrowed non-fungibles are reported by id, apart from the fungible amounts
+    let balances: Balances = common::component_call(
+        &mut test_runner,
+        project_address,
+        "balances",
+        manifest_args!(),
+    );
+    assert!(balances.fungibles == HashMap::from([(app.resource_address, dec!(0))]));
+    assert!(
+        balances.non_fungibles
+            == HashMap::from([(
+                nft_address,
+                indexset!(
+                    NonFungibleLocalId::integer(2),
+                    NonFungibleLocalId::integer(3)
+                )
+            )])
+    );
+
+    // objectives only assigning non-fungibles can't be disputed
+    common::component_test(
+        &mut test_runner,
+        app.admin.clone(),
+        project_address,
+        "request_cancellation",
+        manifest_args!(),
+    );
+    project_dispute(
+        &mut test_runner,
+        app.member.clone(),
+        project_address,
+        dec!(2),
+    )
+    .expect_commit_failure();
+    // the unrewarded non-fungibles are returned from escrow
+    test_runner.advance_to_round_at_timestamp(Round::of(1), 1695236716000i64 + 1000);
+    project_finalize(&mut test_runner, app.admin.clone(), project_address).expect_commit_success();
+
+    let p_state: ProjectState = test_runner.component_state(project_address);
+    assert!(*p_state.withdrawn.get(&app.resource_address).unwrap() == dec!(1000));
+    assert!(p_state.withdrawn.get(&nft_address).is_none());
+    // the withdrawn non-fungible is recorded by id with no amount
+    let txs: Vec<TxData> = common::component_call(
+        &mut test_runner,
+        project_address,
+        "txs_by_badge",
+        manifest_args!(app.member.resource_address, 1u64, 30u64),
+    );
+    let nft_tx = txs
+        .iter()
+        .find(|tx| tx.tx_type == TxType::Withdraw && tx.resource_address == Some(nft_address))
+        .unwrap();
+    assert!(nft_tx.amount == dec!(0));
+    assert!(nft_tx.ids == indexset!(NonFungibleLocalId::integer(1)));
+    // the fully rewarded resource has nothing left to cancel, only the escrow is returned
+    let txs: Vec<TxData> = common::component_call(
+        &mut test_runner,
+        project_address,
+        "txs_by_badge",
+        manifest_args!(app.admin.resource_address, 1u64, 50u64),
+    );
+    let cancelled: Vec<Option<ResourceAddress>> = txs
+        .iter()
+        .filter(|tx| tx.tx_type == TxType::Cancellation)
+        .map(|tx| tx.resource_address)
+        .collect();
+    assert!(cancelled == vec![Some(nft_address)]);
+    let year_data: Option<YearData> = common::component_call(
+        &mut test_runner,
+        project_address,
+        "tx_year",
+        manifest_args!(dec!(2023)),
+    );
+    let year_data = year_data.unwrap();
+    assert!(year_data.withdrawn == HashMap::from([(app.resource_address, dec!(1000))]));
+    assert!(test_runner.get_component_balance(app.member.account_address, nft_address) == dec!(1));
+    assert!(test_runner.get_component_balance(app.admin.account_address, nft_address) == dec!(2));
+}
+
+#[test]
+fn test_notice_period() {
+    let (mut test_runner, app) = common::setup_test();
+    // 30 day notice period
+    let project_address = create_project(
+        &mut test_runner,
+        app.package_address,
+        app.admin.clone(),
+        app.resource_address,
+        None,
+        2592000i64,
+    );
+    common::component_test(
+        &mut test_runner,
+        app.admin.clone(),
+        project_address,
+        "invite",
+        manifest_args!(app.member.resource_address, "handle_2"),
+    );
+    project_join(&mut test_runner, app.member.clone(), project_address);
+    project_deposit(
+        &mut test_runner,
+        app.admin.clone(),
+        app.resource_address,
+        dec!(1000),
+        project_address,
+    );
+    let objs = HashMap::from([(
+        dec!(1),
+        HashMap::from([(
+            app.member.resource_address,
+            HashMap::from([(app.resource_address, dec!(400))]),
+        )]),
+    )]);
+    common::component_test(
+        &mut test_runner,
+        app.admin.clone(),
+        project_address,
+        "update",
+        manifest_args!(objs, NftObjectives::new()),
+    );
+    project_apply(&mut test_runner, app.arbiter.clone(), project_address);
+    project_request_finalize(&mut test_runner, app.admin.clone(), project_address)
+        .expect_commit_failure();
+    common::component_test(
+        &mut test_runner,
+        app.admin.clone(),
+        project_address,
+        "request_cancellation",
+        manifest_args!(),
+    );
+    // applications can't be accepted once a cancellation is requested
+    common::component_receipt(
+        &mut test_runner,
+        app.admin.clone(),
+        project_address,
+        "accept_application",
+        manifest_args!(app.arbiter.resource_address),
+    )
+    .expect_commit_failure();
+    project_finalize(&mut test_runner, app.admin.clone(), project_address).expect_commit_failure();
+
+    // the pending objective can still be rewarded during the notice period
+    test_runner.advance_to_round_at_timestamp(Round::of(1), 1695236716000i64 + 10 * 86400000);
+    common::component_test(
+        &mut test_runner,
+        app.admin.clone(),
+        project_address,
+        "reward",
+        manifest_args!(dec!(1)),
+    );
+    project_withdraw(&mut test_runner, app.member.clone(), project_address);
+    project_finalize(&mut test_runner, app.admin.clone(), project_address).expect_commit_failure();
+    let p_state: ProjectState = test_runner.component_state(project_address);
+    assert!(!p_state.is_cancelled);
+
+    test_runner.advance_to_round_at_timestamp(Round::of(2), 1695236716000i64 + 30 * 86400000);
+    project_finalize(&mut test_runner, app.admin.clone(), project_address).expect_commit_success();
+
+    let p_state: ProjectState = test_runner.component_state(project_address);
+    assert!(p_state.is_cancelled);
+    assert!(*p_state.withdrawn.get(&app.resource_address).unwrap() == dec!(400));
+    // a finalized cancellation can't run again
+    project_finalize(&mut test_runner, app.admin.clone(), project_address).expect_commit_failure();
+    // the admin is returned the remaining 600 of the 11000 minted
+    let balance =
+        test_runner.get_component_balance(app.admin.account_address, app.resource_address);
+    assert!(balance == dec!(10600));
+}
+
+#[test]
+fn test_percent_fee() {
+    let (mut test_runner, app) = common::setup_test();
+    common::component_test(
+        &mut test_runner,
+        app.admin.clone(),
+        app.marketplace_address,
+        "add_category",
+        manifest_args!(
+            vec!["Main"],
+            Some(ContractKind::Project),
+            dec!(100),
+            HashMap::from([(
+                app.resource_address,
+                ListingFee::Percent {
+                    percent: dec!(1),
+                    min: Some(dec!(10)),
+                    max: Some(dec!(20)),
+                }
+            )]),
+            app.resource_address
+        ),
+    );
+
+    // 1% of 500 is clamped to the min, 1% of 3000 to the max, overpayment is returned
+    for (deposit, fee) in [(dec!(500), dec!(10)), (dec!(3000), dec!(20))] {
+        let project_address = create_project(
+            &mut test_runner,
+            app.package_address,
+            app.admin.clone(),
+            app.resource_address,
+            None,
+            1i64,
+        );
+        project_deposit(
+            &mut test_runner,
+            app.admin.clone(),
+            app.resource_address,
+            deposit,
+            project_address,
+        );
+        let balance =
+            test_runner.get_component_balance(app.admin.account_address, app.resource_address);
+        project_list_with_fee(
+            &mut test_runner,
+            app.admin.clone(),
+            project_address,
+            app.marketplace_address,
+            None,
+            app.resource_address,
+            dec!(25),
+        );
+        let paid = balance
+            - test_runner.get_component_balance(app.admin.account_address, app.resource_address);
+        assert!(paid == fee);
+    }
+}